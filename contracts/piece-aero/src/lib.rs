@@ -1,18 +1,172 @@
 #![no_std]
 use soroban_sdk::{
     contract, contractimpl, contracttype, contracterror,
-    Address, Env, String, Map, Symbol, Vec, 
-    symbol_short, log
+    Address, BytesN, Env, String, Map, Symbol, Vec,
+    symbol_short, log, xdr::ToXdr
 };
 
 // Définition des symboles pour les clés de stockage
 const ADMINS: Symbol = symbol_short!("ADMINS");
-const OEM_ORGS: Symbol = symbol_short!("OEM_ORGS");
-const MRO_ORGS: Symbol = symbol_short!("MRO_ORGS");
-const PARTS: Symbol = symbol_short!("PARTS");
+const THRESHOLD: Symbol = symbol_short!("THRESHOLD"); // Nombre d'approbations requis
+const PROPOSAL_SEQ: Symbol = symbol_short!("PROP_SEQ");
+const SCHEMA_VERSION: Symbol = symbol_short!("VERSION"); // Version du schéma de stockage
+const PAUSED: Symbol = symbol_short!("PAUSED"); // Coupe-circuit d'urgence
+
+// Clés de l'ancien schéma (version 0), tout en stockage d'instance ; lues uniquement par migrate
+const LEGACY_PARTS: Symbol = symbol_short!("PARTS");
+const LEGACY_OEM_ORGS: Symbol = symbol_short!("OEM_ORGS");
+const LEGACY_MRO_ORGS: Symbol = symbol_short!("MRO_ORGS");
+
+// Version du schéma de stockage attendue par ce code
+// 0 : pièces et organisations dans le stockage d'instance (PARTS, OEM_ORGS, MRO_ORGS)
+// 1 : une entrée persistante par pièce et par organisation, index paginés
+const CURRENT_SCHEMA_VERSION: u32 = 1;
+
+// Symboles des topics d'événements (stables : les indexeurs s'y abonnent)
+const EVT_INIT: Symbol = symbol_short!("init");
+const EVT_ADMIN_ADDED: Symbol = symbol_short!("adm_add");
+const EVT_ADMIN_REMOVED: Symbol = symbol_short!("adm_rm");
+const EVT_ADMIN_TRANSFERRED: Symbol = symbol_short!("adm_xfer");
+const EVT_THRESHOLD_SET: Symbol = symbol_short!("threshold");
+const EVT_PROPOSED: Symbol = symbol_short!("prop_new");
+const EVT_PROPOSAL_APPROVED: Symbol = symbol_short!("prop_appr");
+const EVT_PROPOSAL_EXECUTED: Symbol = symbol_short!("prop_exec");
+const EVT_UPGRADED: Symbol = symbol_short!("upgraded");
+const EVT_MIGRATED: Symbol = symbol_short!("migrated");
+const EVT_PAUSED: Symbol = symbol_short!("paused");
+const EVT_UNPAUSED: Symbol = symbol_short!("unpaused");
+const EVT_ORG_REGISTERED: Symbol = symbol_short!("org_reg");
+const EVT_ORG_SUSPENDED: Symbol = symbol_short!("org_susp");
+const EVT_ORG_REACTIVATED: Symbol = symbol_short!("org_react");
+const EVT_ORG_UPDATED: Symbol = symbol_short!("org_upd");
+const EVT_ORG_DEREGISTERED: Symbol = symbol_short!("org_dereg");
+const EVT_ROLE_ADDED: Symbol = symbol_short!("role_add");
+const EVT_ROLE_REMOVED: Symbol = symbol_short!("role_rm");
+const EVT_PART_CREATED: Symbol = symbol_short!("created");
+const EVT_TRANSFERRED: Symbol = symbol_short!("transfer");
+const EVT_OFFERED: Symbol = symbol_short!("offer");
+const EVT_OFFER_REJECTED: Symbol = symbol_short!("offer_rej");
+const EVT_OFFER_CANCELLED: Symbol = symbol_short!("offer_can");
+const EVT_STATUS_UPDATED: Symbol = symbol_short!("status");
+const EVT_DOCUMENT_ADDED: Symbol = symbol_short!("doc_add");
+const EVT_DOCUMENT_SUPERSEDED: Symbol = symbol_short!("doc_sup");
+const EVT_DOCUMENT_REVOKED: Symbol = symbol_short!("doc_rev");
+const EVT_STAFF_KEY_ADDED: Symbol = symbol_short!("staff_add");
+const EVT_STAFF_KEY_REVOKED: Symbol = symbol_short!("staff_rm");
+const EVT_USAGE_RECORDED: Symbol = symbol_short!("usage");
+const EVT_ADJUSTMENT_REQUESTED: Symbol = symbol_short!("usage_req");
+const EVT_ADJUSTMENT_APPROVED: Symbol = symbol_short!("usage_adj");
+const EVT_ADJUSTMENT_REJECTED: Symbol = symbol_short!("usage_rej");
+const EVT_SHOP_VISIT: Symbol = symbol_short!("shop_vis");
+const EVT_WORK_ORDER_OPENED: Symbol = symbol_short!("wo_open");
+const EVT_WORK_ORDER_CLOSED: Symbol = symbol_short!("wo_close");
+const EVT_MAINTENANCE_REASSIGNED: Symbol = symbol_short!("mro_xfer");
+
+// Durée de vie du stockage (5 ans en nombre de ledgers, estimation)
+// Avec un ledger toutes les 5 secondes: 5 ans ≈ 31,536,000 secondes / 5 = 6,307,200 ledgers
+const TTL_THRESHOLD: u32 = 1000;
+const TTL_EXTEND_TO: u32 = 6_307_200;
+
+// Durée de validité d'une proposition de gouvernance (7 jours, en secondes)
+const PROPOSAL_LIFETIME: u64 = 7 * 24 * 60 * 60;
+
+// Taille maximale d'une page de listing : chaque élément est une entrée de stockage lue,
+// à garder sous la limite d'entrées lues par transaction du réseau
+const MAX_PAGE_SIZE: u32 = 25;
+
+// Taille maximale d'une page dont chaque élément coûte deux lectures (lien de la liste puis fiche)
+const MAX_JOINED_PAGE_SIZE: u32 = 12;
+
+// Longueurs maximales (en octets) des métadonnées de document
+const MAX_DOCUMENT_NAME_LEN: u32 = 64;
+const MAX_DOCUMENT_URI_LEN: u32 = 256;
+
+// Durée de validité d'une signature de personnel certifiant (24 heures, en secondes)
+const SIGNATURE_VALIDITY: u64 = 24 * 60 * 60;
+
+// Clés du stockage persistant (une entrée par pièce et par élément d'index)
+#[derive(Clone)]
+#[contracttype]
+pub enum DataKey {
+    Part(String),                // UID -> AeronauticPart
+    IndexMeta(IndexKey),         // Taille, premier et dernier UID d'un index
+    IndexNode(IndexKey, String), // UID -> voisins dans l'index (liste doublement chaînée)
+    HistoryLen(String),          // UID -> Nombre d'entrées d'historique
+    History(String, u32),        // UID et numéro d'entrée -> HistoryRecord
+    Offer(String),               // UID -> TransferOffer en attente
+    Adjustment(String),          // UID -> UsageAdjustment en attente
+    LifeLimits(String),          // UID -> LifeLimits (pièces à durée de vie limitée)
+    LastOverhaul(String),        // UID -> (heures, cycles) totaux à la dernière révision
+    ShopVisitLen(String),        // UID -> nombre de visites atelier
+    ShopVisit(String, u32),      // UID et numéro de visite -> ShopVisit
+    MaintenanceLen(String),      // UID -> nombre d'ordres de travail
+    Maintenance(String, u32),    // UID et numéro d'ordre -> MaintenanceRecord
+    OpenWorkOrder(String),       // UID -> numéro de l'ordre de travail en cours
+    AssignedMro(String),         // UID -> MRO qui a la pièce en maintenance
+    QuarantineHold(String),      // UID -> épisode de maintenance ouvert depuis la quarantaine (remise en service exigée)
+    DocumentLen(String),         // UID -> nombre de documents (toutes versions)
+    Document(String, u32),       // UID et identifiant -> DocumentRecord
+    DocumentVersion(String, String, u32), // UID, nom et version -> identifiant
+    DocumentLatest(String, String),       // UID et nom -> identifiant de la dernière version
+    DocumentByDigest(String, BytesN<32>), // UID et empreinte -> identifiant du dernier document portant cette empreinte
+    StaffKey(Address, String),            // Organisation et identifiant du personnel -> clé publique ed25519
+    Org(Address),                // Adresse -> Organization
+    OrgHistoryLen(Address),      // Adresse -> Nombre d'entrées d'historique
+    OrgHistory(Address, u32),    // Adresse et numéro d'entrée -> OrgRecord
+    RoleCount(OrgType),          // Rôle -> nombre d'organisations enregistrées qui le détiennent
+    OrgDirectory,                // Taille, première et dernière adresse de l'annuaire
+    OrgNode(Address),            // Adresse -> voisins dans l'annuaire (liste doublement chaînée)
+    Proposal(u32),               // Identifiant -> Proposal
+}
+
+// Index de pièces maintenus on-chain
+#[derive(Clone)]
+#[contracttype]
+pub enum IndexKey {
+    All,                               // Toutes les pièces, dans l'ordre de création
+    Owner(Address),                    // Propriétaire -> pièces détenues
+    Manufacturer(Address),             // Fabricant -> pièces fabriquées
+    Status(PartStatus),                // Statut -> pièces
+    OwnerStatus(Address, PartStatus),  // Propriétaire et statut -> pièces
+    Maintainer(Address),               // MRO -> pièces qu'il a en maintenance
+}
+
+// En-tête d'un index de pièces (liste chaînée dans l'ordre d'insertion)
+#[derive(Clone)]
+#[contracttype]
+pub struct IndexMeta {
+    pub len: u32,
+    pub head: Option<String>,
+    pub tail: Option<String>,
+}
+
+// Élément d'un index : UID précédent et suivant
+#[derive(Clone)]
+#[contracttype]
+pub struct IndexNode {
+    pub prev: Option<String>,
+    pub next: Option<String>,
+}
+
+// En-tête de l'annuaire des organisations (liste chaînée dans l'ordre d'enregistrement)
+#[derive(Clone)]
+#[contracttype]
+pub struct OrgDirectory {
+    pub len: u32,
+    pub head: Option<Address>,
+    pub tail: Option<Address>,
+}
+
+// Élément de l'annuaire : adresse précédente et suivante
+#[derive(Clone)]
+#[contracttype]
+pub struct OrgNode {
+    pub prev: Option<Address>,
+    pub next: Option<Address>,
+}
 
 // Types d'organisations autorisées
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq)]
 #[contracttype]
 pub enum OrgType {
     OEM,     // Original Equipment Manufacturer
@@ -20,12 +174,95 @@ pub enum OrgType {
     Airline, // Compagnie aérienne
     Lessor,  // Société de leasing
     Distributor, // Distributeur certifié
+    Authority,   // Autorité de navigabilité (EASA, FAA, DGAC...)
 }
 
 // Structure d'une organisation
 #[derive(Clone)]
 #[contracttype]
 pub struct Organization {
+    pub id: Address,
+    pub name: String,
+    pub org_type: OrgType,           // Type principal, fixé à l'enregistrement
+    pub extra_roles: Vec<OrgType>,   // Agréments supplémentaires (ex : OEM également MRO)
+    pub certificates: Vec<String>,
+    pub active: bool,
+}
+
+impl Organization {
+    // L'organisation détient-elle ce rôle (type principal ou agrément supplémentaire) ?
+    pub fn has_role(&self, role: OrgType) -> bool {
+        self.org_type == role || self.extra_roles.contains(role)
+    }
+}
+
+// Action administrative sur une organisation
+#[derive(Clone, PartialEq)]
+#[contracttype]
+pub enum OrgAction {
+    Suspended,
+    Reactivated,
+    Updated,
+    Deregistered,
+    RoleAdded(OrgType),
+    RoleRemoved(OrgType),
+}
+
+// Entrée de l'historique d'une organisation, avec sa justification
+#[derive(Clone)]
+#[contracttype]
+pub struct OrgRecord {
+    pub seq: u32,
+    pub action: OrgAction,
+    pub actor: Address,
+    pub reason: String,
+    pub timestamp: u64, // Timestamp Unix
+}
+
+// Page de l'historique d'une organisation
+#[contracttype]
+#[derive(Clone)]
+pub struct OrgHistoryPage {
+    pub records: Vec<OrgRecord>,
+    pub next_cursor: Option<u32>,
+}
+
+// Action d'administration soumise au vote des administrateurs
+#[derive(Clone)]
+#[contracttype]
+pub enum ProposalAction {
+    RegisterOrg(Address, String, OrgType, Vec<String>), // Adresse, nom, type, certificats
+    SuspendOrg(Address, String),                        // Adresse, justification
+    ReactivateOrg(Address, String),                     // Adresse, justification
+    UpdateOrg(Address, String, Vec<String>, String),    // Adresse, nom, certificats, justification
+    DeregisterOrg(Address, String),                     // Adresse, justification
+    AddOrgRole(Address, OrgType, String),               // Adresse, rôle, justification
+    RemoveOrgRole(Address, OrgType, String),            // Adresse, rôle, justification
+    AddAdmin(Address),
+    RemoveAdmin(Address),
+    TransferAdmin(Address, Address),                    // Administrateur remplacé, nouvel administrateur
+    SetThreshold(u32),
+    Upgrade(BytesN<32>),                                // Hash du nouveau WASM
+    Unpause,
+}
+
+// Proposition de gouvernance, exécutée dès que le seuil d'approbations est atteint
+#[derive(Clone)]
+#[contracttype]
+pub struct Proposal {
+    pub id: u32,
+    pub proposer: Address,
+    pub action: ProposalAction,
+    pub approvals: Vec<Address>,
+    pub created_at: u64, // Timestamp Unix
+    pub expires_at: u64, // Timestamp Unix
+    pub executed: bool,
+}
+
+// Organisation telle que stockée dans le schéma version 0 (sans agréments supplémentaires)
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyOrganization {
     pub id: Address,
     pub name: String,
     pub org_type: OrgType,
@@ -33,6 +270,23 @@ pub struct Organization {
     pub active: bool,
 }
 
+// Pièce telle que stockée dans le schéma version 0 (documents réduits à un hash par nom)
+#[derive(Clone)]
+#[contracttype]
+pub struct LegacyAeronauticPart {
+    pub uid: String,
+    pub part_number: String,
+    pub serial_number: String,
+    pub manufacturer: Address,
+    pub date_of_manufacture: u64,
+    pub current_owner: Address,
+    pub status: PartStatus,
+    pub total_hours: u32,
+    pub total_cycles: u32,
+    pub last_updated: u64,
+    pub document_hashes: Map<String, String>, // Nom du document -> Hash
+}
+
 // Statut d'une pièce
 #[derive(Clone, Copy, PartialEq)]
 #[contracttype]
@@ -57,7 +311,263 @@ pub struct AeronauticPart {
     pub total_hours: u32,
     pub total_cycles: u32,
     pub last_updated: u64, // Timestamp Unix
-    pub document_hashes: Map<String, String>, // Nom du document -> Hash
+}
+
+// Limites de vie certifiées d'une pièce à durée de vie limitée (LLP), fixées par l'OEM
+// None : pas de limite sur ce critère
+#[contracttype]
+#[derive(Clone)]
+pub struct LifeLimits {
+    pub max_hours: Option<u32>,
+    pub max_cycles: Option<u32>,
+    pub max_age: Option<u64>, // Âge calendaire maximal en secondes depuis la fabrication
+}
+
+// Potentiel restant d'une pièce (None : pas de limite sur ce critère)
+#[contracttype]
+#[derive(Clone)]
+pub struct RemainingLife {
+    pub hours: Option<u32>,
+    pub cycles: Option<u32>,
+    pub seconds: Option<u64>,
+    pub limit_reached: bool, // Une limite est atteinte : retrait à acter par enforce_life_limits s'il n'a pas eu lieu
+}
+
+// Compteurs d'utilisation d'une pièce
+#[contracttype]
+#[derive(Clone)]
+pub struct UsageCounters {
+    pub tsn: u32, // Heures depuis neuf (Time Since New)
+    pub csn: u32, // Cycles depuis neuf (Cycles Since New)
+    pub tso: u32, // Heures depuis la dernière révision (Time Since Overhaul)
+    pub cso: u32, // Cycles depuis la dernière révision (Cycles Since Overhaul)
+}
+
+// Type de visite atelier
+#[derive(Clone, Copy, PartialEq)]
+#[contracttype]
+pub enum ShopVisitType {
+    Overhaul,   // Révision générale : remet à zéro TSO/CSO
+    Repair,
+    Inspection,
+}
+
+// Visite atelier d'une pièce (jamais modifiée une fois écrite)
+#[derive(Clone)]
+#[contracttype]
+pub struct ShopVisit {
+    pub seq: u32,
+    pub visit_type: ShopVisitType,
+    pub mro: Address,
+    pub workscope_doc_hash: BytesN<32>, // Empreinte du dossier de travaux (workscope)
+    pub timestamp: u64,             // Timestamp Unix
+    pub tsn: u32,                   // Compteurs au moment de la visite
+    pub csn: u32,
+    pub tso: u32,
+    pub cso: u32,
+}
+
+// Page de visites atelier
+#[contracttype]
+#[derive(Clone)]
+pub struct ShopVisitPage {
+    pub visits: Vec<ShopVisit>,
+    pub next_cursor: Option<u32>,
+}
+
+// Type de tâche de maintenance
+#[derive(Clone, Copy, PartialEq)]
+#[contracttype]
+pub enum MaintenanceTaskType {
+    Inspection,
+    Repair,
+    Overhaul,
+    Modification,
+}
+
+// Ordre de travail de maintenance sur une pièce
+#[derive(Clone)]
+#[contracttype]
+pub struct MaintenanceRecord {
+    pub seq: u32,
+    pub work_order_id: String,
+    pub task_type: MaintenanceTaskType,
+    pub mro: Address,                   // MRO qui réalise les travaux
+    pub certifying_staff: String,       // Référence du personnel certifiant (licence Part-66...)
+    pub started_at: u64,                // Timestamp Unix
+    pub completed_at: Option<u64>,      // None tant que l'ordre est ouvert
+    pub findings_hash: Option<BytesN<32>>, // Empreinte du rapport de constatations, à la clôture
+    pub hours_at_task: u32,
+    pub cycles_at_task: u32,
+}
+
+// Page d'ordres de travail
+#[contracttype]
+#[derive(Clone)]
+pub struct MaintenancePage {
+    pub records: Vec<MaintenanceRecord>,
+    pub next_cursor: Option<u32>,
+}
+
+// Type de document réglementaire ou technique
+#[derive(Clone, Copy, PartialEq)]
+#[contracttype]
+pub enum DocumentType {
+    EasaForm1,               // Certificat de remise en service EASA Form 1
+    FaaForm8130,             // Certificat de navigabilité FAA 8130-3
+    CertificateOfConformity, // Certificat de conformité (CofC)
+    WorkOrder,
+    InspectionReport,
+    Photo,
+    Legacy,                  // Document repris du schéma version 0 (type non renseigné)
+}
+
+// Algorithme ayant produit l'empreinte d'un document (empreinte de 32 octets)
+#[derive(Clone, Copy, PartialEq)]
+#[contracttype]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha3_256,
+    Keccak256,
+    Blake2b256,
+}
+
+// Document soumis au contrat (création, ajout, remplacement ou remise en service)
+#[derive(Clone)]
+#[contracttype]
+pub struct DocumentInput {
+    pub name: String,
+    pub doc_type: DocumentType,
+    pub hash_algorithm: HashAlgorithm,
+    pub digest: BytesN<32>,
+    pub uri: Option<String>, // Emplacement hors chaîne (URL, CID IPFS...)
+}
+
+// Signature ed25519 d'un document par un membre du personnel certifiant de l'organisation,
+// portant sur l'encodage XDR du tuple (uid, empreinte, timestamp)
+#[derive(Clone)]
+#[contracttype]
+pub struct StaffAttestation {
+    pub staff_id: String,       // Identifiant du personnel (ex : numéro de licence Part-66)
+    pub timestamp: u64,         // Timestamp Unix de la signature
+    pub signature: BytesN<64>,
+}
+
+// Version d'un document attaché à une pièce (registre append-only : jamais écrasée)
+#[derive(Clone)]
+#[contracttype]
+pub struct DocumentRecord {
+    pub id: u32,            // Identifiant unique du document pour la pièce
+    pub name: String,
+    pub version: u32,       // 1 pour le document initial, +1 à chaque remplacement
+    pub doc_type: DocumentType,
+    pub hash_algorithm: HashAlgorithm,
+    pub digest: BytesN<32>,
+    pub uri: Option<String>,
+    pub uploader: Address,
+    pub timestamp: u64,     // Timestamp Unix
+    pub revoked: bool,
+    pub revocation_reason: Option<BytesN<32>>, // Empreinte du motif de révocation
+    pub signed_by: Option<String>,        // Personnel certifiant ayant signé le document (vérifié à l'ajout)
+    pub signed_at: Option<u64>,           // Timestamp Unix de la signature
+    pub signature: Option<BytesN<64>>,
+    pub signer_key: Option<BytesN<32>>,   // Clé publique ayant vérifié la signature (re-vérifiable après rotation)
+}
+
+// Résultat public de la vérification d'un document : rien d'autre sur la pièce n'est exposé
+#[derive(Clone)]
+#[contracttype]
+pub struct DocumentVerification {
+    pub registered: bool,          // L'empreinte est enregistrée pour cette pièce
+    pub name: Option<String>,      // Champs renseignés uniquement si l'empreinte est enregistrée
+    pub version: Option<u32>,
+    pub uploader: Option<Address>,
+    pub timestamp: Option<u64>,
+    pub revoked: bool,
+}
+
+// Page de documents
+#[contracttype]
+#[derive(Clone)]
+pub struct DocumentPage {
+    pub documents: Vec<DocumentRecord>,
+    pub next_cursor: Option<u32>,
+}
+
+// Correction de compteurs demandée par un MRO, en attente d'approbation par un administrateur
+#[contracttype]
+#[derive(Clone)]
+pub struct UsageAdjustment {
+    pub mro: Address,
+    pub previous_hours: u32,  // total_hours attendu au moment de l'approbation
+    pub previous_cycles: u32, // total_cycles attendu au moment de l'approbation
+    pub hours: u32,        // Nouvelle valeur de total_hours
+    pub cycles: u32,       // Nouvelle valeur de total_cycles
+    pub reason: String,
+    pub requested_at: u64, // Timestamp Unix
+}
+
+// Offre de transfert en attente d'acceptation par le destinataire
+#[contracttype]
+#[derive(Clone)]
+pub struct TransferOffer {
+    pub from: Address,
+    pub to: Address,
+    pub broker: Option<Address>, // Distributeur qui a négocié la revente
+    pub created_at: u64,         // Timestamp Unix
+    pub expires_at: Option<u64>, // Timestamp Unix, pas d'expiration si None
+}
+
+// Événement de l'historique d'une pièce (valeurs avant -> après)
+#[derive(Clone)]
+#[contracttype]
+pub enum HistoryEvent {
+    Created,                                // Fabrication (l'acteur est le fabricant)
+    Transferred(Address, Address),          // Ancien propriétaire -> nouveau propriétaire
+    StatusChanged(PartStatus, PartStatus),  // Ancien statut -> nouveau statut
+    UsageRecorded(u32, u32, u32, u32, String), // Heures, cycles avant -> après, source (carnet de route, ACARS...)
+    UsageAdjusted(u32, u32, u32, u32, String), // Heures, cycles avant -> après, justification
+    DocumentAdded(String, Option<BytesN<32>>, BytesN<32>), // Nom, ancienne empreinte -> nouvelle empreinte
+    ShopVisitRecorded(u32, ShopVisitType),  // Numéro de visite atelier, type
+    WorkOrderOpened(String, MaintenanceTaskType), // Ordre de travail, type de tâche
+    WorkOrderClosed(String, BytesN<32>),    // Ordre de travail, empreinte des constatations
+    DocumentRevoked(u32, BytesN<32>),       // Identifiant du document, empreinte du motif
+    MaintenanceReassigned(Option<Address>, Address, String), // Ancien MRO -> nouveau MRO, motif
+}
+
+// Entrée de l'historique d'une pièce (jamais modifiée une fois écrite)
+#[derive(Clone)]
+#[contracttype]
+pub struct HistoryRecord {
+    pub seq: u32,
+    pub actor: Address,
+    pub timestamp: u64, // Timestamp Unix
+    pub event: HistoryEvent,
+}
+
+// Page de l'historique d'une pièce
+#[contracttype]
+#[derive(Clone)]
+pub struct HistoryPage {
+    pub records: Vec<HistoryRecord>,
+    pub next_cursor: Option<u32>,
+}
+
+// Page de résultats d'un listing
+// `next_cursor` est à repasser en `start_after` pour obtenir la page suivante
+#[contracttype]
+#[derive(Clone)]
+pub struct PartPage {
+    pub uids: Vec<String>,
+    pub next_cursor: Option<String>, // Dernier UID de la page
+}
+
+// Page de résultats du listing des organisations
+#[contracttype]
+#[derive(Clone)]
+pub struct OrgPage {
+    pub orgs: Vec<Organization>,
+    pub next_cursor: Option<Address>, // Adresse de la dernière organisation de la page
 }
 
 // Erreurs possibles - utilisation de contracterror
@@ -71,6 +581,32 @@ pub enum Error {
     PartAlreadyExists = 4,
     PartNotFound = 5,
     InvalidInput = 6,
+    OfferNotFound = 7,
+    OfferExpired = 8,
+    OrgAlreadyRegistered = 9,
+    LastAdmin = 10,
+    MultisigRequired = 11,
+    ProposalNotFound = 12,
+    ProposalExpired = 13,
+    ProposalAlreadyExecuted = 14,
+    AlreadyApproved = 15,
+    ContractPaused = 16,
+    InvalidStatusTransition = 17,
+    ReleaseDocumentRequired = 18,
+    UsageOverflow = 19,
+    AdjustmentNotFound = 20,
+    LifeLimitExceeded = 21,
+    WorkOrderAlreadyOpen = 22,
+    WorkOrderNotFound = 23,
+    DocumentAlreadyExists = 24,
+    DocumentNotFound = 25,
+    DocumentAlreadyRevoked = 26,
+    StaffKeyNotFound = 27,
+    SignatureExpired = 28,
+    AdjustmentPending = 29,
+    AdjustmentStale = 30,
+    CursorNotFound = 31,
+    MigrationPending = 32,
 }
 
 #[contract]
@@ -78,381 +614,2791 @@ pub struct PartsRegistry;
 
 #[contractimpl]
 impl PartsRegistry {
-    // Fonction d'initialisation du contrat
-    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
-        // Vérifier que le contrat n'est pas déjà initialisé
-        if env.storage().instance().has(&ADMINS) {
-            return Err(Error::InvalidInput);
-        }
-        
-        // Vérifier l'identité de l'administrateur
-        admin.require_auth();
-        
+    // Initialisation du contrat, exécutée une seule fois lors du déploiement
+    // (aucune fenêtre entre déploiement et initialisation où un tiers pourrait s'emparer du registre)
+    pub fn __constructor(env: Env, admin: Address) {
         // Enregistrer l'administrateur
         let mut admins = Vec::new(&env);
         admins.push_back(admin.clone());
         env.storage().instance().set(&ADMINS, &admins);
         
-        // Initialiser les collections
-        let oem_orgs: Vec<Organization> = Vec::new(&env);
-        let mro_orgs: Vec<Organization> = Vec::new(&env);
-        let parts: Map<String, AeronauticPart> = Map::new(&env);
+        // Pièces et organisations vivent dans le stockage persistant
+        env.storage().instance().set(&SCHEMA_VERSION, &CURRENT_SCHEMA_VERSION);
         
-        env.storage().instance().set(&OEM_ORGS, &oem_orgs);
-        env.storage().instance().set(&MRO_ORGS, &mro_orgs);
-        env.storage().instance().set(&PARTS, &parts);
-        
-        // Étendre la durée de vie du stockage (5 ans en nombre de ledgers, estimation)
-        // Avec un ledger toutes les 5 secondes: 5 ans ≈ 31,536,000 secondes / 5 = 6,307,200 ledgers
-        env.storage().instance().extend_ttl(1000, 6_307_200);
+        // Étendre la durée de vie du stockage
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
         
         log!(&env, "Contract initialized with admin: {}", admin);
-        Ok(())
+        env.events().publish((EVT_INIT,), admin);
     }
     
-    // Enregistrer une nouvelle organisation OEM
-    pub fn register_oem(
-        env: Env, 
-        caller: Address, 
-        org_address: Address, 
-        name: String, 
-        certificates: Vec<String>
-    ) -> Result<(), Error> {
-        // Vérifier que l'appelant est un administrateur
+    // Ajouter un administrateur
+    pub fn add_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_add_admin(&env, &caller, new_admin)
+    }
+    
+    // Retirer un administrateur (le dernier administrateur ne peut pas être retiré)
+    pub fn remove_admin(env: Env, caller: Address, admin: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_remove_admin(&env, &caller, admin)
+    }
+    
+    // Céder son rôle d'administrateur à une autre adresse (qui doit co-signer)
+    pub fn transfer_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), Error> {
+        caller.require_auth();
+        new_admin.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_transfer_admin(&env, caller.clone(), new_admin)
+    }
+    
+    // Vérifier si une adresse est administrateur
+    pub fn is_admin(env: Env, address: Address) -> bool {
+        Self::ensure_is_admin(&env, &address).is_ok()
+    }
+    
+    // Définir le nombre d'approbations requis pour les actions d'administration
+    // (au-delà de 1, toute action d'administration passe par une proposition)
+    pub fn set_threshold(env: Env, caller: Address, threshold: u32) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_set_threshold(&env, &caller, threshold)
+    }
+    
+    // Obtenir le nombre d'approbations requis
+    pub fn get_threshold(env: Env) -> u32 {
+        Self::threshold(&env)
+    }
+    
+    // Proposer une action d'administration ; l'auteur l'approuve d'office
+    pub fn propose(env: Env, caller: Address, action: ProposalAction) -> Result<u32, Error> {
         caller.require_auth();
         Self::ensure_is_admin(&env, &caller)?;
         
-        // Créer l'organisation
-        let org = Organization {
-            id: org_address.clone(),
-            name,
-            org_type: OrgType::OEM,
-            certificates,
-            active: true,
+        let id: u32 = env.storage().instance().get(&PROPOSAL_SEQ).unwrap_or(0);
+        env.storage().instance().set(&PROPOSAL_SEQ, &(id + 1));
+        
+        let current_time = env.ledger().timestamp();
+        let mut approvals = Vec::new(&env);
+        approvals.push_back(caller.clone());
+        let proposal = Proposal {
+            id,
+            proposer: caller.clone(),
+            action,
+            approvals,
+            created_at: current_time,
+            expires_at: current_time + PROPOSAL_LIFETIME,
+            executed: false,
         };
         
-        // Récupérer et mettre à jour la liste des OEMs
-        let mut oem_orgs: Vec<Organization> = env.storage().instance().get(&OEM_ORGS).unwrap_or(Vec::new(&env));
-        oem_orgs.push_back(org);
-        env.storage().instance().set(&OEM_ORGS, &oem_orgs);
+        log!(&env, "Admin {} created proposal: {}", caller, id);
+        env.events().publish((EVT_PROPOSED, id), (caller, proposal.action.clone()));
         
-        log!(&env, "Registered new OEM: {}", org_address);
-        Ok(())
+        Self::try_execute(&env, proposal)?;
+        Ok(id)
     }
     
-    // Enregistrer une nouvelle organisation MRO
-    pub fn register_mro(
-        env: Env, 
-        caller: Address, 
-        org_address: Address, 
-        name: String, 
-        certificates: Vec<String>
-    ) -> Result<(), Error> {
-        // Vérifier que l'appelant est un administrateur
+    // Approuver une proposition ; renvoie true si elle a été exécutée
+    pub fn approve_proposal(env: Env, caller: Address, proposal_id: u32) -> Result<bool, Error> {
         caller.require_auth();
         Self::ensure_is_admin(&env, &caller)?;
         
-        // Créer l'organisation
-        let org = Organization {
-            id: org_address.clone(),
-            name,
-            org_type: OrgType::MRO,
-            certificates,
-            active: true,
-        };
+        let mut proposal: Proposal = env.storage().persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .ok_or(Error::ProposalNotFound)?;
         
-        // Récupérer et mettre à jour la liste des MROs
-        let mut mro_orgs: Vec<Organization> = env.storage().instance().get(&MRO_ORGS).unwrap_or(Vec::new(&env));
-        mro_orgs.push_back(org);
-        env.storage().instance().set(&MRO_ORGS, &mro_orgs);
+        if proposal.executed {
+            return Err(Error::ProposalAlreadyExecuted);
+        }
+        if env.ledger().timestamp() > proposal.expires_at {
+            return Err(Error::ProposalExpired);
+        }
+        if proposal.approvals.contains(&caller) {
+            return Err(Error::AlreadyApproved);
+        }
         
-        log!(&env, "Registered new MRO: {}", org_address);
-        Ok(())
+        proposal.approvals.push_back(caller.clone());
+        
+        log!(&env, "Admin {} approved proposal: {}", caller, proposal_id);
+        env.events().publish((EVT_PROPOSAL_APPROVED, proposal_id), caller);
+        
+        Self::try_execute(&env, proposal)
     }
     
-    // Créer une nouvelle pièce aéronautique
-    pub fn create_part(
-        env: Env,
-        manufacturer: Address,
-        uid: String,
-        part_number: String,
-        serial_number: String,
-        document_hashes: Map<String, String>
-    ) -> Result<(), Error> {
-        // Vérifier l'autorisation du fabricant
-        manufacturer.require_auth();
+    // Obtenir une proposition de gouvernance
+    pub fn get_proposal(env: Env, proposal_id: u32) -> Result<Proposal, Error> {
+        env.storage().persistent().get(&DataKey::Proposal(proposal_id)).ok_or(Error::ProposalNotFound)
+    }
+    
+    // Suspendre en urgence toutes les écritures (création, transferts, statuts, documents, enregistrements)
+    // Un seul administrateur suffit, même sous gouvernance multi-signatures ; les lectures restent possibles
+    pub fn pause(env: Env, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_is_admin(&env, &caller)?;
         
-        // Vérifier que le fabricant est un OEM enregistré
-        Self::ensure_is_oem(&env, &manufacturer)?;
+        if Self::paused(&env) {
+            return Err(Error::InvalidInput);
+        }
+        
+        env.storage().instance().set(&PAUSED, &true);
+        
+        log!(&env, "Contract paused by admin: {}", caller);
+        env.events().publish((EVT_PAUSED,), caller);
+        Ok(())
+    }
+    
+    // Lever la pause (proposition ProposalAction::Unpause si le seuil dépasse 1)
+    pub fn unpause(env: Env, caller: Address) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_unpause(&env, &caller)
+    }
+    
+    // Le contrat est-il en pause ?
+    pub fn is_paused(env: Env) -> bool {
+        Self::paused(&env)
+    }
+    
+    // Remplacer le code du contrat (le WASM doit avoir été téléversé au préalable)
+    // Le stockage est conservé ; appeler ensuite migrate si le schéma a changé
+    pub fn upgrade(env: Env, caller: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_upgrade(&env, &caller, new_wasm_hash)
+    }
+    
+    // Version du schéma de stockage actuellement en place (0 pour un déploiement antérieur aux versions)
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage().instance().get(&SCHEMA_VERSION).unwrap_or(0)
+    }
+    
+    // Réécrire le stockage dans le schéma attendu par le code actuel, par lots d'au plus `limit` pièces
+    // À rappeler tant que la version renvoyée est inférieure à la version courante : la version
+    // n'est incrémentée qu'après le dernier lot, et les écritures restent refusées jusque-là
+    pub fn migrate(env: Env, caller: Address, limit: u32) -> Result<u32, Error> {
+        caller.require_auth();
+        Self::ensure_is_admin(&env, &caller)?;
+        if limit == 0 {
+            return Err(Error::InvalidInput);
+        }
+        
+        let from_version = Self::get_schema_version(env.clone());
+        if from_version >= CURRENT_SCHEMA_VERSION {
+            return Ok(from_version);
+        }
+        
+        let finished = match from_version {
+            0 => Self::migrate_v0_to_v1(&env, limit),
+            _ => true,
+        };
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        if !finished {
+            return Ok(from_version);
+        }
+        
+        let version = from_version + 1;
+        env.storage().instance().set(&SCHEMA_VERSION, &version);
+        
+        log!(&env, "Admin {} migrated storage from version {} to {}", caller, from_version, version);
+        env.events().publish((EVT_MIGRATED,), (caller, from_version, version));
+        Ok(version)
+    }
+    
+    // Enregistrer une nouvelle organisation, quel que soit son type
+    pub fn register_organization(
+        env: Env,
+        caller: Address,
+        org_address: Address,
+        name: String,
+        org_type: OrgType,
+        certificates: Vec<String>
+    ) -> Result<(), Error> {
+        // Vérifier que l'appelant est un administrateur
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_register_organization(&env, &caller, org_address, name, org_type, certificates)
+    }
+    
+    // Enregistrer une nouvelle organisation OEM
+    pub fn register_oem(
+        env: Env, 
+        caller: Address, 
+        org_address: Address, 
+        name: String, 
+        certificates: Vec<String>
+    ) -> Result<(), Error> {
+        Self::register_organization(env, caller, org_address, name, OrgType::OEM, certificates)
+    }
+    
+    // Enregistrer une nouvelle organisation MRO
+    pub fn register_mro(
+        env: Env, 
+        caller: Address, 
+        org_address: Address, 
+        name: String, 
+        certificates: Vec<String>
+    ) -> Result<(), Error> {
+        Self::register_organization(env, caller, org_address, name, OrgType::MRO, certificates)
+    }
+    
+    // Obtenir la fiche publique d'une organisation
+    pub fn get_organization(env: Env, org_address: Address) -> Result<Organization, Error> {
+        Self::load_org(&env, &org_address).ok_or(Error::OrgNotRegistered)
+    }
+    
+    // Suspendre une organisation (ex : perte d'agrément Part-145)
+    pub fn suspend_organization(
+        env: Env,
+        caller: Address,
+        org_address: Address,
+        reason: String
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_suspend_organization(&env, &caller, org_address, reason)
+    }
+    
+    // Réactiver une organisation suspendue
+    pub fn reactivate_organization(
+        env: Env,
+        caller: Address,
+        org_address: Address,
+        reason: String
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_reactivate_organization(&env, &caller, org_address, reason)
+    }
+    
+    // Mettre à jour le nom et les certificats d'une organisation
+    pub fn update_organization(
+        env: Env,
+        caller: Address,
+        org_address: Address,
+        name: String,
+        certificates: Vec<String>,
+        reason: String
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_update_organization(&env, &caller, org_address, name, certificates, reason)
+    }
+    
+    // Retirer une organisation de l'annuaire (son historique est conservé)
+    pub fn deregister_organization(
+        env: Env,
+        caller: Address,
+        org_address: Address,
+        reason: String
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_deregister_organization(&env, &caller, org_address, reason)
+    }
+    
+    // Ajouter un agrément supplémentaire à une organisation déjà enregistrée
+    pub fn add_org_role(
+        env: Env,
+        caller: Address,
+        org_address: Address,
+        role: OrgType,
+        reason: String
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_add_org_role(&env, &caller, org_address, role, reason)
+    }
+    
+    // Retirer un agrément supplémentaire (le type principal ne peut pas être retiré)
+    pub fn remove_org_role(
+        env: Env,
+        caller: Address,
+        org_address: Address,
+        role: OrgType,
+        reason: String
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_direct_admin(&env, &caller)?;
+        
+        Self::apply_remove_org_role(&env, &caller, org_address, role, reason)
+    }
+    
+    // Obtenir l'historique administratif d'une organisation, page par page
+    pub fn get_organization_history(
+        env: Env,
+        org_address: Address,
+        start_after: Option<u32>,
+        limit: u32
+    ) -> Result<OrgHistoryPage, Error> {
+        let len: u32 = env.storage().persistent().get(&DataKey::OrgHistoryLen(org_address.clone())).unwrap_or(0);
+        let (start, end) = Self::page_bounds(start_after, limit, len)?;
+        
+        let mut records = Vec::new(&env);
+        for i in start..end {
+            if let Some(record) = env.storage().persistent().get(&DataKey::OrgHistory(org_address.clone(), i)) {
+                records.push_back(record);
+            }
+        }
+        
+        let next_cursor = if end < len { Some(end - 1) } else { None };
+        Ok(OrgHistoryPage { records, next_cursor })
+    }
+    
+    // Enregistrer (ou remplacer) la clé publique ed25519 d'un membre du personnel certifiant
+    pub fn register_staff_key(
+        env: Env,
+        org_address: Address,
+        staff_id: String,
+        public_key: BytesN<32>
+    ) -> Result<(), Error> {
+        org_address.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        match Self::load_org(&env, &org_address) {
+            Some(org) if org.active => {},
+            _ => return Err(Error::OrgNotRegistered),
+        }
+        if staff_id.is_empty() || staff_id.len() > MAX_DOCUMENT_NAME_LEN {
+            return Err(Error::InvalidInput);
+        }
+        
+        let key = DataKey::StaffKey(org_address.clone(), staff_id.clone());
+        env.storage().persistent().set(&key, &public_key);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        log!(&env, "Organization {} registered staff key for: {}", org_address, staff_id);
+        env.events().publish((EVT_STAFF_KEY_ADDED, org_address), (staff_id, public_key));
+        Ok(())
+    }
+    
+    // Retirer la clé d'un membre du personnel (mesure corrective : disponible même en pause) ;
+    // les documents déjà signés conservent leur attestation
+    pub fn revoke_staff_key(env: Env, org_address: Address, staff_id: String) -> Result<(), Error> {
+        org_address.require_auth();
+        Self::ensure_schema_current(&env)?;
+        
+        let key = DataKey::StaffKey(org_address.clone(), staff_id.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::StaffKeyNotFound);
+        }
+        env.storage().persistent().remove(&key);
+        
+        log!(&env, "Organization {} revoked staff key for: {}", org_address, staff_id);
+        env.events().publish((EVT_STAFF_KEY_REVOKED, org_address), staff_id);
+        Ok(())
+    }
+    
+    // Obtenir la clé publique d'un membre du personnel d'une organisation
+    pub fn get_staff_key(env: Env, org_address: Address, staff_id: String) -> Result<BytesN<32>, Error> {
+        env.storage().persistent()
+            .get(&DataKey::StaffKey(org_address, staff_id))
+            .ok_or(Error::StaffKeyNotFound)
+    }
+    
+    // Créer une nouvelle pièce aéronautique
+    pub fn create_part(
+        env: Env,
+        manufacturer: Address,
+        uid: String,
+        part_number: String,
+        serial_number: String,
+        documents: Vec<DocumentInput>,
+        life_limits: Option<LifeLimits>
+    ) -> Result<(), Error> {
+        // Vérifier l'autorisation du fabricant
+        manufacturer.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        // Vérifier que le fabricant est un OEM enregistré
+        Self::ensure_is_oem(&env, &manufacturer)?;
         
         // Vérifier que la pièce n'existe pas déjà
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        if parts.contains_key(uid.clone()) {
+        if env.storage().persistent().has(&DataKey::Part(uid.clone())) {
             return Err(Error::PartAlreadyExists);
         }
         
-        // Créer la pièce
-        let current_time = env.ledger().timestamp();
-        let part = AeronauticPart {
-            uid: uid.clone(),
-            part_number,
-            serial_number,
-            manufacturer: manufacturer.clone(),
-            date_of_manufacture: current_time,
-            current_owner: manufacturer.clone(), // Le fabricant est le propriétaire initial
-            status: PartStatus::Active,
-            total_hours: 0,
-            total_cycles: 0,
-            last_updated: current_time,
-            document_hashes,
-        };
-        
-        // Ajouter la pièce au registre
-        let mut updated_parts = parts.clone();
-        updated_parts.set(uid.clone(), part);
-        env.storage().instance().set(&PARTS, &updated_parts);
-        
-        // Prolonger la durée de vie du stockage
-        env.storage().instance().extend_ttl(1000, 6_307_200);
+        // Une limite de vie déclarée doit être strictement positive
+        if let Some(limits) = &life_limits {
+            if limits.max_hours == Some(0) || limits.max_cycles == Some(0) || limits.max_age == Some(0) {
+                return Err(Error::InvalidInput);
+            }
+        }
+        
+        // Valider les documents initiaux (un seul document par nom)
+        for (i, document) in documents.iter().enumerate() {
+            Self::validate_document(&document)?;
+            if documents.iter().skip(i + 1).any(|other| other.name == document.name) {
+                return Err(Error::InvalidInput);
+            }
+        }
+        
+        // Créer la pièce
+        let current_time = env.ledger().timestamp();
+        let mut part = AeronauticPart {
+            uid: uid.clone(),
+            part_number: part_number.clone(),
+            serial_number: serial_number.clone(),
+            manufacturer: manufacturer.clone(),
+            date_of_manufacture: current_time,
+            current_owner: manufacturer.clone(), // Le fabricant est le propriétaire initial
+            status: PartStatus::Active,
+            total_hours: 0,
+            total_cycles: 0,
+            last_updated: current_time,
+        };
+        
+        // Ajouter la pièce au registre et aux index
+        Self::save_part(&env, &part);
+        Self::index_push(&env, IndexKey::All, &uid);
+        Self::index_push(&env, IndexKey::Manufacturer(manufacturer.clone()), &uid);
+        Self::index_push(&env, IndexKey::Owner(manufacturer.clone()), &uid);
+        Self::index_push(&env, IndexKey::Status(PartStatus::Active), &uid);
+        Self::index_push(&env, IndexKey::OwnerStatus(manufacturer.clone(), PartStatus::Active), &uid);
+        
+        // Enregistrer les limites de vie (LLP)
+        if let Some(limits) = life_limits {
+            let key = DataKey::LifeLimits(uid.clone());
+            env.storage().persistent().set(&key, &limits);
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+        
+        // Ouvrir l'historique de la pièce
+        Self::record_history(&env, &uid, &manufacturer, HistoryEvent::Created);
+        
+        log!(&env, "Created new part: {} by manufacturer: {}", uid, manufacturer);
+        env.events().publish((EVT_PART_CREATED, uid), (manufacturer.clone(), part_number, serial_number));
+        
+        // Enregistrer les documents initiaux (version 1 de chacun)
+        if !documents.is_empty() {
+            for document in documents.iter() {
+                Self::append_document(&env, &mut part, document, None, &manufacturer);
+            }
+            Self::save_part(&env, &part);
+        }
+        
+        // Prolonger la durée de vie du stockage
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+    
+    // Obtenir les informations d'une pièce avec son statut enregistré ; une limite de vie atteinte
+    // avant l'appel à enforce_life_limits est signalée par remaining_life (limit_reached)
+    pub fn get_part(env: Env, uid: String) -> Result<AeronauticPart, Error> {
+        Self::load_part(&env, &uid)
+    }
+    
+    // Obtenir l'historique d'une pièce, page par page, de la fabrication à aujourd'hui
+    pub fn get_part_history(
+        env: Env,
+        uid: String,
+        start_after: Option<u32>,
+        limit: u32
+    ) -> Result<HistoryPage, Error> {
+        // Vérifier que la pièce existe
+        Self::load_part(&env, &uid)?;
+        
+        let len: u32 = env.storage().persistent().get(&DataKey::HistoryLen(uid.clone())).unwrap_or(0);
+        let (start, end) = Self::page_bounds(start_after, limit, len)?;
+        
+        let mut records = Vec::new(&env);
+        for i in start..end {
+            if let Some(record) = env.storage().persistent().get(&DataKey::History(uid.clone(), i)) {
+                records.push_back(record);
+            }
+        }
+        
+        let next_cursor = if end < len { Some(end - 1) } else { None };
+        Ok(HistoryPage { records, next_cursor })
+    }
+    
+    // Transférer la propriété d'une pièce en une seule opération
+    // (le nouveau propriétaire doit co-signer la transaction)
+    pub fn transfer_ownership(
+        env: Env,
+        current_owner: Address,
+        new_owner: Address,
+        uid: String
+    ) -> Result<(), Error> {
+        // Vérifier l'autorisation du propriétaire actuel et du destinataire
+        current_owner.require_auth();
+        new_owner.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        // Récupérer la pièce (vérifie qu'elle existe)
+        let part = Self::load_part(&env, &uid)?;
+        
+        // Vérifier que l'appelant est bien le propriétaire actuel
+        if part.current_owner != current_owner {
+            return Err(Error::NotAuthorized);
+        }
+        Self::ensure_within_life_limits(&env, &part)?;
+        
+        Self::apply_transfer(&env, part, &new_owner, &current_owner);
+        Ok(())
+    }
+    
+    // Proposer le transfert d'une pièce à un destinataire, qui devra l'accepter
+    pub fn offer_transfer(
+        env: Env,
+        current_owner: Address,
+        new_owner: Address,
+        uid: String,
+        expires_at: Option<u64>
+    ) -> Result<(), Error> {
+        // Vérifier l'autorisation du propriétaire actuel
+        current_owner.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        // Récupérer la pièce (vérifie qu'elle existe)
+        let part = Self::load_part(&env, &uid)?;
+        
+        // Vérifier que l'appelant est bien le propriétaire actuel
+        if part.current_owner != current_owner {
+            return Err(Error::NotAuthorized);
+        }
+        Self::ensure_within_life_limits(&env, &part)?;
+        
+        Self::store_offer(&env, &uid, current_owner, new_owner, expires_at, None)
+    }
+    
+    // Proposer la revente d'une pièce par l'intermédiaire d'un distributeur
+    // (le propriétaire et le distributeur signent, le destinataire accepte ensuite)
+    pub fn broker_transfer(
+        env: Env,
+        distributor: Address,
+        current_owner: Address,
+        new_owner: Address,
+        uid: String,
+        expires_at: Option<u64>
+    ) -> Result<(), Error> {
+        // Vérifier l'autorisation du distributeur et du propriétaire
+        distributor.require_auth();
+        current_owner.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        // Seuls les distributeurs certifiés peuvent négocier une revente
+        Self::ensure_has_role(&env, &distributor, OrgType::Distributor)?;
+        
+        // Récupérer la pièce (vérifie qu'elle existe)
+        let part = Self::load_part(&env, &uid)?;
+        
+        // Vérifier que le vendeur est bien le propriétaire actuel
+        if part.current_owner != current_owner {
+            return Err(Error::NotAuthorized);
+        }
+        Self::ensure_within_life_limits(&env, &part)?;
+        
+        Self::store_offer(&env, &uid, current_owner, new_owner, expires_at, Some(distributor))
+    }
+    
+    // Accepter une offre de transfert (le destinataire prend la garde de la pièce)
+    pub fn accept_transfer(env: Env, new_owner: Address, uid: String) -> Result<(), Error> {
+        // Vérifier l'autorisation du destinataire
+        new_owner.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        let offer = Self::load_offer(&env, &uid)?;
+        if offer.to != new_owner {
+            return Err(Error::NotAuthorized);
+        }
+        
+        // Vérifier que l'offre n'a pas expiré
+        if let Some(expiry) = offer.expires_at {
+            if env.ledger().timestamp() > expiry {
+                return Err(Error::OfferExpired);
+            }
+        }
+        
+        // Vérifier que l'auteur de l'offre possède toujours la pièce
+        let part = Self::load_part(&env, &uid)?;
+        if part.current_owner != offer.from {
+            return Err(Error::NotAuthorized);
+        }
+        Self::ensure_within_life_limits(&env, &part)?;
+        
+        Self::apply_transfer(&env, part, &new_owner, &new_owner);
+        Ok(())
+    }
+    
+    // Refuser une offre de transfert (par le destinataire)
+    pub fn reject_transfer(env: Env, new_owner: Address, uid: String) -> Result<(), Error> {
+        new_owner.require_auth();
+        Self::ensure_schema_current(&env)?;
+        
+        let offer = Self::load_offer(&env, &uid)?;
+        if offer.to != new_owner {
+            return Err(Error::NotAuthorized);
+        }
+        
+        env.storage().persistent().remove(&DataKey::Offer(uid.clone()));
+        
+        log!(&env, "Rejected transfer of part: {} by: {}", uid, new_owner);
+        env.events().publish((EVT_OFFER_REJECTED, uid), (offer.from, new_owner));
+        Ok(())
+    }
+    
+    // Annuler une offre de transfert (par le propriétaire qui l'a émise)
+    pub fn cancel_transfer(env: Env, current_owner: Address, uid: String) -> Result<(), Error> {
+        current_owner.require_auth();
+        Self::ensure_schema_current(&env)?;
+        
+        let offer = Self::load_offer(&env, &uid)?;
+        if offer.from != current_owner {
+            return Err(Error::NotAuthorized);
+        }
+        
+        env.storage().persistent().remove(&DataKey::Offer(uid.clone()));
+        
+        log!(&env, "Cancelled transfer of part: {} by: {}", uid, current_owner);
+        env.events().publish((EVT_OFFER_CANCELLED, uid), (current_owner, offer.to));
+        Ok(())
+    }
+    
+    // Obtenir l'offre de transfert en attente pour une pièce
+    pub fn get_transfer_offer(env: Env, uid: String) -> Result<TransferOffer, Error> {
+        Self::load_offer(&env, &uid)
+    }
+    
+    // Mettre à jour le statut d'une pièce (pour maintenance)
+    pub fn update_part_status(
+        env: Env,
+        authorized_org: Address,
+        uid: String,
+        new_status: PartStatus
+    ) -> Result<(), Error> {
+        // Vérifier l'autorisation de l'organisation
+        authorized_org.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        // Vérifier que l'organisation est un MRO, une autorité ou le propriétaire
+        Self::ensure_can_update_status(&env, &authorized_org, &uid)?;
+        
+        // Récupérer la pièce (vérifie qu'elle existe)
+        let part = Self::load_part(&env, &uid)?;
+        
+        // Vérifier que le changement de statut est autorisé
+        Self::ensure_maintenance_holder(&env, &uid, &authorized_org)?;
+        if new_status != PartStatus::InMaintenance {
+            Self::ensure_no_open_work_order(&env, &uid)?;
+        }
+        Self::ensure_status_transition(&env, &part, &authorized_org, new_status)?;
+        
+        // Mettre à jour le statut (les compteurs ne changent que via record_usage)
+        let current_time = env.ledger().timestamp();
+        let mut updated_part = part.clone();
+        updated_part.status = new_status;
+        updated_part.last_updated = current_time;
+        
+        // Mettre à jour le registre et, si le statut change, les index de statut
+        Self::save_part(&env, &updated_part);
+        if part.status != new_status {
+            Self::reindex_status(&env, &uid, &part.current_owner, part.status, new_status, &authorized_org);
+            
+            Self::record_history(
+                &env,
+                &uid,
+                &authorized_org,
+                HistoryEvent::StatusChanged(part.status, new_status)
+            );
+        }
+        
+        // Prolonger la durée de vie du stockage
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        log!(&env, "Updated status of part: {} to: {:?} by: {}", uid, new_status, authorized_org);
+        env.events().publish(
+            (EVT_STATUS_UPDATED, uid),
+            (authorized_org, new_status, part.total_hours, part.total_cycles)
+        );
+        Ok(())
+    }
+    
+    // Ajouter des heures de vol et des cycles à une pièce (les compteurs ne peuvent qu'augmenter)
+    pub fn record_usage(
+        env: Env,
+        authorized_org: Address,
+        uid: String,
+        delta_hours: u32,
+        delta_cycles: u32,
+        source: String
+    ) -> Result<(), Error> {
+        authorized_org.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        if (delta_hours == 0 && delta_cycles == 0) || source.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+        
+        // Seuls le propriétaire et le MRO qui a la pièce en maintenance relèvent l'utilisation
+        let mut part = Self::load_part(&env, &uid)?;
+        Self::ensure_custodian(&env, &part, &authorized_org)?;
+        if part.status == PartStatus::Retired {
+            return Err(Error::InvalidStatusTransition);
+        }
+        let (previous_hours, previous_cycles) = (part.total_hours, part.total_cycles);
+        part.total_hours = part.total_hours.checked_add(delta_hours).ok_or(Error::UsageOverflow)?;
+        part.total_cycles = part.total_cycles.checked_add(delta_cycles).ok_or(Error::UsageOverflow)?;
+        part.last_updated = env.ledger().timestamp();
+        
+        // Une pièce à durée de vie limitée ne peut pas dépasser ses limites d'heures et de cycles ;
+        // elle est retirée du service dès qu'une limite (y compris l'âge calendaire) est atteinte
+        let life_expired = Self::check_life_limits(&env, &part)?;
+        // Pendant un ordre de travail, le retrait se fait à sa clôture (seul statut de retour permis)
+        let previous_status = part.status;
+        if life_expired && !Self::has_open_work_order(&env, &uid) {
+            part.status = PartStatus::Retired;
+        }
+        
+        Self::save_part(&env, &part);
+        Self::record_history(
+            &env,
+            &uid,
+            &authorized_org,
+            HistoryEvent::UsageRecorded(
+                previous_hours,
+                previous_cycles,
+                part.total_hours,
+                part.total_cycles,
+                source.clone()
+            )
+        );
+        if part.status != previous_status {
+            Self::reindex_status(&env, &uid, &part.current_owner, previous_status, part.status, &authorized_org);
+            Self::record_history(
+                &env,
+                &uid,
+                &authorized_org,
+                HistoryEvent::StatusChanged(previous_status, part.status)
+            );
+            
+            log!(&env, "Part: {} reached its life limit and was retired", uid);
+            env.events().publish(
+                (EVT_STATUS_UPDATED, uid.clone()),
+                (authorized_org.clone(), part.status, part.total_hours, part.total_cycles)
+            );
+        }
+        
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        log!(&env, "Recorded usage on part: {} (+{}h, +{} cycles) by: {}", uid, delta_hours, delta_cycles, authorized_org);
+        env.events().publish(
+            (EVT_USAGE_RECORDED, uid),
+            (authorized_org, delta_hours, delta_cycles, source)
+        );
+        Ok(())
+    }
+    
+    // Obtenir les compteurs depuis neuf (TSN/CSN) et depuis la dernière révision (TSO/CSO)
+    pub fn get_usage_counters(env: Env, uid: String) -> Result<UsageCounters, Error> {
+        let part = Self::load_part(&env, &uid)?;
+        Ok(Self::usage_counters(&env, &part))
+    }
+    
+    // Enregistrer une visite atelier (MRO uniquement) ; une révision générale remet TSO/CSO à zéro,
+    // les totaux depuis neuf ne changent pas
+    pub fn record_shop_visit(
+        env: Env,
+        mro: Address,
+        uid: String,
+        visit_type: ShopVisitType,
+        workscope_doc_hash: BytesN<32>
+    ) -> Result<u32, Error> {
+        mro.require_auth();
+        Self::ensure_not_paused(&env)?;
+        Self::ensure_is_mro(&env, &mro)?;
+        Self::ensure_valid_digest(&workscope_doc_hash)?;
+        
+        // Seul le MRO qui a la pièce en maintenance enregistre une visite atelier
+        let part = Self::load_part(&env, &uid)?;
+        if part.status != PartStatus::InMaintenance {
+            return Err(Error::InvalidStatusTransition);
+        }
+        if Self::assigned_mro(&env, &uid) != Some(mro.clone()) {
+            return Err(Error::NotAuthorized);
+        }
+        let counters = Self::usage_counters(&env, &part);
+        
+        // Ajouter la visite au registre des visites de la pièce
+        let len_key = DataKey::ShopVisitLen(uid.clone());
+        let seq: u32 = env.storage().persistent().get(&len_key).unwrap_or(0);
+        let visit_key = DataKey::ShopVisit(uid.clone(), seq);
+        let visit = ShopVisit {
+            seq,
+            visit_type,
+            mro: mro.clone(),
+            workscope_doc_hash: workscope_doc_hash.clone(),
+            timestamp: env.ledger().timestamp(),
+            tsn: counters.tsn,
+            csn: counters.csn,
+            tso: counters.tso,
+            cso: counters.cso,
+        };
+        env.storage().persistent().set(&visit_key, &visit);
+        env.storage().persistent().set(&len_key, &(seq + 1));
+        env.storage().persistent().extend_ttl(&visit_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage().persistent().extend_ttl(&len_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        // Une révision générale repart de zéro pour TSO/CSO
+        if visit_type == ShopVisitType::Overhaul {
+            let key = DataKey::LastOverhaul(uid.clone());
+            env.storage().persistent().set(&key, &(part.total_hours, part.total_cycles));
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+        
+        Self::record_history(&env, &uid, &mro, HistoryEvent::ShopVisitRecorded(seq, visit_type));
+        
+        log!(&env, "MRO {} recorded shop visit {} on part: {}", mro, seq, uid);
+        env.events().publish((EVT_SHOP_VISIT, uid), (mro, visit_type, workscope_doc_hash));
+        Ok(seq)
+    }
+    
+    // Obtenir les visites atelier d'une pièce, page par page
+    pub fn get_shop_visits(
+        env: Env,
+        uid: String,
+        start_after: Option<u32>,
+        limit: u32
+    ) -> Result<ShopVisitPage, Error> {
+        // Vérifier que la pièce existe
+        Self::load_part(&env, &uid)?;
+        
+        let len: u32 = env.storage().persistent().get(&DataKey::ShopVisitLen(uid.clone())).unwrap_or(0);
+        let (start, end) = Self::page_bounds(start_after, limit, len)?;
+        
+        let mut visits = Vec::new(&env);
+        for i in start..end {
+            if let Some(visit) = env.storage().persistent().get(&DataKey::ShopVisit(uid.clone(), i)) {
+                visits.push_back(visit);
+            }
+        }
+        
+        let next_cursor = if end < len { Some(end - 1) } else { None };
+        Ok(ShopVisitPage { visits, next_cursor })
+    }
+    
+    // Ouvrir un ordre de travail (MRO uniquement) : la pièce passe en maintenance
+    pub fn open_work_order(
+        env: Env,
+        mro: Address,
+        uid: String,
+        work_order_id: String,
+        task_type: MaintenanceTaskType,
+        certifying_staff: String
+    ) -> Result<u32, Error> {
+        mro.require_auth();
+        Self::ensure_not_paused(&env)?;
+        Self::ensure_is_mro(&env, &mro)?;
+        
+        if work_order_id.is_empty() || certifying_staff.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+        
+        // Un seul ordre de travail ouvert à la fois par pièce
+        Self::ensure_no_open_work_order(&env, &uid)?;
+        
+        let mut part = Self::load_part(&env, &uid)?;
+        Self::ensure_maintenance_holder(&env, &uid, &mro)?;
+        Self::ensure_status_transition(&env, &part, &mro, PartStatus::InMaintenance)?;
+        
+        let len_key = DataKey::MaintenanceLen(uid.clone());
+        let seq: u32 = env.storage().persistent().get(&len_key).unwrap_or(0);
+        let record = MaintenanceRecord {
+            seq,
+            work_order_id: work_order_id.clone(),
+            task_type,
+            mro: mro.clone(),
+            certifying_staff,
+            started_at: env.ledger().timestamp(),
+            completed_at: None,
+            findings_hash: None,
+            hours_at_task: part.total_hours,
+            cycles_at_task: part.total_cycles,
+        };
+        Self::save_maintenance_record(&env, &uid, &record);
+        env.storage().persistent().set(&len_key, &(seq + 1));
+        env.storage().persistent().extend_ttl(&len_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        let open_key = DataKey::OpenWorkOrder(uid.clone());
+        env.storage().persistent().set(&open_key, &seq);
+        env.storage().persistent().extend_ttl(&open_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        Self::record_history(&env, &uid, &mro, HistoryEvent::WorkOrderOpened(work_order_id.clone(), task_type));
+        if part.status == PartStatus::InMaintenance {
+            // Pièce déjà en maintenance sans MRO affecté : le MRO qui ouvre l'ordre la prend en charge
+            if Self::assigned_mro(&env, &uid).is_none() {
+                Self::assign_maintainer(&env, &uid, &mro);
+            }
+        } else {
+            Self::change_status(&env, &mut part, PartStatus::InMaintenance, &mro);
+        }
+        
+        log!(&env, "MRO {} opened work order: {} on part: {}", mro, work_order_id, uid);
+        env.events().publish((EVT_WORK_ORDER_OPENED, uid), (mro, work_order_id, task_type));
+        Ok(seq)
+    }
+    
+    // Clôturer l'ordre de travail en cours (MRO qui l'a ouvert) : la pièce quitte la maintenance
+    // avec le statut indiqué (Active, Quarantined ou Retired)
+    pub fn close_work_order(
+        env: Env,
+        mro: Address,
+        uid: String,
+        findings_hash: BytesN<32>,
+        return_status: PartStatus
+    ) -> Result<(), Error> {
+        mro.require_auth();
+        Self::ensure_not_paused(&env)?;
+        Self::ensure_is_mro(&env, &mro)?;
+        
+        Self::ensure_valid_digest(&findings_hash)?;
+        if return_status == PartStatus::InMaintenance {
+            return Err(Error::InvalidInput);
+        }
+        
+        let mut record = Self::load_open_work_order(&env, &uid)?;
+        if record.mro != mro {
+            return Err(Error::NotAuthorized);
+        }
+        
+        let mut part = Self::load_part(&env, &uid)?;
+        Self::ensure_status_transition(&env, &part, &mro, return_status)?;
+        
+        record.completed_at = Some(env.ledger().timestamp());
+        record.findings_hash = Some(findings_hash.clone());
+        Self::save_maintenance_record(&env, &uid, &record);
+        env.storage().persistent().remove(&DataKey::OpenWorkOrder(uid.clone()));
+        
+        Self::record_history(
+            &env,
+            &uid,
+            &mro,
+            HistoryEvent::WorkOrderClosed(record.work_order_id.clone(), findings_hash.clone())
+        );
+        Self::change_status(&env, &mut part, return_status, &mro);
+        
+        log!(&env, "MRO {} closed work order: {} on part: {}", mro, record.work_order_id, uid);
+        env.events().publish(
+            (EVT_WORK_ORDER_CLOSED, uid),
+            (mro, record.work_order_id, findings_hash, return_status)
+        );
+        Ok(())
+    }
+    
+    // Confier une pièce en maintenance à un autre MRO (administrateur ou autorité), par exemple
+    // lorsque le MRO affecté est suspendu ou retiré de l'annuaire ; l'ordre de travail en cours le suit
+    pub fn reassign_maintenance(
+        env: Env,
+        caller: Address,
+        uid: String,
+        new_mro: Address,
+        reason: String
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        if Self::ensure_is_admin(&env, &caller).is_err()
+            && Self::ensure_has_role(&env, &caller, OrgType::Authority).is_err() {
+            return Err(Error::NotAuthorized);
+        }
+        Self::ensure_is_mro(&env, &new_mro)?;
+        
+        let part = Self::load_part(&env, &uid)?;
+        if part.status != PartStatus::InMaintenance {
+            return Err(Error::InvalidStatusTransition);
+        }
+        let previous_mro = Self::assigned_mro(&env, &uid);
+        if previous_mro.as_ref() == Some(&new_mro) {
+            return Err(Error::InvalidInput);
+        }
+        
+        Self::release_maintainer(&env, &uid);
+        Self::assign_maintainer(&env, &uid, &new_mro);
+        if let Ok(mut record) = Self::load_open_work_order(&env, &uid) {
+            record.mro = new_mro.clone();
+            Self::save_maintenance_record(&env, &uid, &record);
+        }
+        
+        Self::record_history(
+            &env,
+            &uid,
+            &caller,
+            HistoryEvent::MaintenanceReassigned(previous_mro.clone(), new_mro.clone(), reason.clone())
+        );
+        
+        log!(&env, "Reassigned maintenance of part: {} to MRO: {} by: {}", uid, new_mro, caller);
+        env.events().publish((EVT_MAINTENANCE_REASSIGNED, uid), (caller, previous_mro, new_mro, reason));
+        Ok(())
+    }
+    
+    // Obtenir le MRO qui a la pièce en maintenance
+    pub fn get_assigned_mro(env: Env, uid: String) -> Result<Option<Address>, Error> {
+        // Vérifier que la pièce existe
+        Self::load_part(&env, &uid)?;
+        Ok(Self::assigned_mro(&env, &uid))
+    }
+    
+    // Obtenir l'ordre de travail en cours sur une pièce
+    pub fn get_open_work_order(env: Env, uid: String) -> Result<MaintenanceRecord, Error> {
+        Self::load_open_work_order(&env, &uid)
+    }
+    
+    // Obtenir les ordres de travail d'une pièce, page par page
+    pub fn get_maintenance_records(
+        env: Env,
+        uid: String,
+        start_after: Option<u32>,
+        limit: u32
+    ) -> Result<MaintenancePage, Error> {
+        // Vérifier que la pièce existe
+        Self::load_part(&env, &uid)?;
+        
+        let len: u32 = env.storage().persistent().get(&DataKey::MaintenanceLen(uid.clone())).unwrap_or(0);
+        let (start, end) = Self::page_bounds(start_after, limit, len)?;
+        
+        let mut records = Vec::new(&env);
+        for i in start..end {
+            if let Some(record) = env.storage().persistent().get(&DataKey::Maintenance(uid.clone(), i)) {
+                records.push_back(record);
+            }
+        }
+        
+        let next_cursor = if end < len { Some(end - 1) } else { None };
+        Ok(MaintenancePage { records, next_cursor })
+    }
+    
+    // Retirer du service une pièce qui a atteint une limite de vie (notamment l'âge calendaire,
+    // qui s'écoule sans relevé) ; ouvert à tous, renvoie vrai si la pièce vient d'être retirée
+    pub fn enforce_life_limits(env: Env, uid: String) -> Result<bool, Error> {
+        Self::ensure_not_paused(&env)?;
+        
+        let mut part = Self::load_part(&env, &uid)?;
+        if part.status == PartStatus::Retired || !Self::life_limit_reached(&env, &part) {
+            return Ok(false);
+        }
+        Self::ensure_no_open_work_order(&env, &uid)?;
+        
+        log!(&env, "Part: {} reached its life limit and was retired", uid);
+        Self::change_status(&env, &mut part, PartStatus::Retired, &env.current_contract_address());
+        Ok(true)
+    }
+    
+    // Obtenir le potentiel restant d'une pièce avant ses limites de vie
+    pub fn remaining_life(env: Env, uid: String) -> Result<RemainingLife, Error> {
+        let part = Self::load_part(&env, &uid)?;
+        let limits: Option<LifeLimits> = env.storage().persistent().get(&DataKey::LifeLimits(uid));
+        
+        let remaining = match limits {
+            Some(limits) => {
+                let age = env.ledger().timestamp().saturating_sub(part.date_of_manufacture);
+                RemainingLife {
+                    hours: limits.max_hours.map(|max| max.saturating_sub(part.total_hours)),
+                    cycles: limits.max_cycles.map(|max| max.saturating_sub(part.total_cycles)),
+                    seconds: limits.max_age.map(|max| max.saturating_sub(age)),
+                    limit_reached: Self::life_limit_reached(&env, &part),
+                }
+            },
+            None => RemainingLife { hours: None, cycles: None, seconds: None, limit_reached: false },
+        };
+        Ok(remaining)
+    }
+    
+    // Demander la correction des compteurs d'une pièce (MRO uniquement) ;
+    // la correction ne s'applique qu'après approbation d'un administrateur
+    pub fn request_usage_adjustment(
+        env: Env,
+        mro: Address,
+        uid: String,
+        hours: u32,
+        cycles: u32,
+        reason: String
+    ) -> Result<(), Error> {
+        mro.require_auth();
+        Self::ensure_not_paused(&env)?;
+        Self::ensure_is_mro(&env, &mro)?;
+        
+        if reason.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+        
+        // Le MRO doit être propriétaire de la pièce ou l'avoir en maintenance
+        let part = Self::load_part(&env, &uid)?;
+        Self::ensure_custodian(&env, &part, &mro)?;
+        
+        // Une demande en attente doit être approuvée ou rejetée avant d'en déposer une autre
+        let key = DataKey::Adjustment(uid.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::AdjustmentPending);
+        }
+        
+        // La demande porte sur les totaux actuels : elle devient caduque s'ils changent entre-temps
+        let adjustment = UsageAdjustment {
+            mro: mro.clone(),
+            previous_hours: part.total_hours,
+            previous_cycles: part.total_cycles,
+            hours,
+            cycles,
+            reason: reason.clone(),
+            requested_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &adjustment);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        log!(&env, "MRO {} requested usage adjustment on part: {}", mro, uid);
+        env.events().publish((EVT_ADJUSTMENT_REQUESTED, uid), (mro, hours, cycles, reason));
+        Ok(())
+    }
+    
+    // Approuver une correction de compteurs (administrateur)
+    pub fn approve_usage_adjustment(env: Env, caller: Address, uid: String) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_not_paused(&env)?;
+        Self::ensure_is_admin(&env, &caller)?;
+        
+        let adjustment = Self::load_adjustment(&env, &uid)?;
+        let mut part = Self::load_part(&env, &uid)?;
+        let (previous_hours, previous_cycles) = (part.total_hours, part.total_cycles);
+        
+        // Un relevé enregistré depuis la demande serait écrasé : la demande est à rejeter et refaire
+        if previous_hours != adjustment.previous_hours || previous_cycles != adjustment.previous_cycles {
+            return Err(Error::AdjustmentStale);
+        }
+        
+        part.total_hours = adjustment.hours;
+        part.total_cycles = adjustment.cycles;
+        part.last_updated = env.ledger().timestamp();
+        Self::save_part(&env, &part);
+        env.storage().persistent().remove(&DataKey::Adjustment(uid.clone()));
+        
+        // La correction est tracée au nom du MRO qui l'a demandée
+        Self::record_history(
+            &env,
+            &uid,
+            &adjustment.mro,
+            HistoryEvent::UsageAdjusted(
+                previous_hours,
+                previous_cycles,
+                adjustment.hours,
+                adjustment.cycles,
+                adjustment.reason
+            )
+        );
+        
+        log!(&env, "Admin {} approved usage adjustment on part: {}", caller, uid);
+        env.events().publish(
+            (EVT_ADJUSTMENT_APPROVED, uid.clone()),
+            (caller.clone(), adjustment.mro, adjustment.hours, adjustment.cycles)
+        );
+        
+        // Des totaux corrigés qui atteignent ou dépassent une limite de vie retirent la pièce du service
+        // (à la clôture de l'ordre de travail s'il y en a un en cours)
+        if part.status != PartStatus::Retired
+            && Self::life_limit_reached(&env, &part)
+            && !Self::has_open_work_order(&env, &uid) {
+            log!(&env, "Part: {} reached its life limit and was retired", uid);
+            Self::change_status(&env, &mut part, PartStatus::Retired, &caller);
+        }
+        Ok(())
+    }
+    
+    // Rejeter une correction de compteurs (administrateur)
+    pub fn reject_usage_adjustment(env: Env, caller: Address, uid: String) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_schema_current(&env)?;
+        Self::ensure_is_admin(&env, &caller)?;
+        
+        let adjustment = Self::load_adjustment(&env, &uid)?;
+        env.storage().persistent().remove(&DataKey::Adjustment(uid.clone()));
+        
+        log!(&env, "Admin {} rejected usage adjustment on part: {}", caller, uid);
+        env.events().publish((EVT_ADJUSTMENT_REJECTED, uid), (caller, adjustment.mro));
+        Ok(())
+    }
+    
+    // Obtenir la correction de compteurs en attente pour une pièce
+    pub fn get_usage_adjustment(env: Env, uid: String) -> Result<UsageAdjustment, Error> {
+        Self::load_adjustment(&env, &uid)
+    }
+    
+    // Remettre en service une pièce en quarantaine (MRO ou autorité uniquement),
+    // en enregistrant le certificat de remise en service (EASA Form 1 ou FAA 8130-3)
+    pub fn release_from_quarantine(
+        env: Env,
+        authorized_org: Address,
+        uid: String,
+        document: DocumentInput,
+        attestation: Option<StaffAttestation>
+    ) -> Result<(), Error> {
+        authorized_org.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        // Seuls un MRO ou une autorité peuvent lever une quarantaine
+        Self::ensure_is_mro_or_authority(&env, &authorized_org)?;
+        
+        // Pièce en quarantaine, ou en maintenance depuis la quarantaine (par le MRO affecté)
+        let mut part = Self::load_part(&env, &uid)?;
+        let from = part.status;
+        match from {
+            PartStatus::Quarantined => {},
+            PartStatus::InMaintenance if Self::quarantine_hold(&env, &uid) => {
+                Self::ensure_maintenance_holder(&env, &uid, &authorized_org)?;
+                Self::ensure_no_open_work_order(&env, &uid)?;
+            },
+            _ => return Err(Error::InvalidStatusTransition),
+        }
+        Self::ensure_within_life_limits(&env, &part)?;
+        if !Self::is_release_document(document.doc_type) {
+            return Err(Error::ReleaseDocumentRequired);
+        }
+        Self::validate_document(&document)?;
+        
+        // Joindre le document de remise en service (nouvelle version s'il existe déjà) et réactiver la pièce
+        let attestation = Self::verify_attestation(&env, &authorized_org, &uid, &document.digest, attestation)?;
+        Self::append_document(&env, &mut part, document, attestation, &authorized_org);
+        Self::change_status(&env, &mut part, PartStatus::Active, &authorized_org);
+        
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        log!(&env, "Released part: {} from {:?} by: {}", uid, from, authorized_org);
+        Ok(())
+    }
+    
+    // Ajouter un nouveau document à une pièce (version 1) ; renvoie son identifiant
+    // Un nom déjà utilisé est refusé : remplacer un document passe par supersede_document
+    // Une attestation éventuelle doit être signée par un membre du personnel de l'organisation
+    pub fn add_document(
+        env: Env,
+        authorized_org: Address,
+        uid: String,
+        document: DocumentInput,
+        attestation: Option<StaffAttestation>
+    ) -> Result<u32, Error> {
+        // Vérifier l'autorisation de l'organisation
+        authorized_org.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        // Vérifier que l'organisation est un MRO, OEM ou le propriétaire
+        Self::ensure_can_add_document(&env, &authorized_org, &uid)?;
+        
+        Self::validate_document(&document)?;
+        
+        // Récupérer la pièce (vérifie qu'elle existe)
+        let mut part = Self::load_part(&env, &uid)?;
+        
+        if env.storage().persistent().has(&DataKey::DocumentLatest(uid.clone(), document.name.clone())) {
+            return Err(Error::DocumentAlreadyExists);
+        }
+        
+        let attestation = Self::verify_attestation(&env, &authorized_org, &uid, &document.digest, attestation)?;
+        let id = Self::append_document(&env, &mut part, document, attestation, &authorized_org);
+        Self::save_part(&env, &part);
+        
+        // Prolonger la durée de vie du stockage
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(id)
+    }
+    
+    // Remplacer un document par une nouvelle version ; les versions précédentes restent consultables
+    // (auteur de la version en vigueur, administrateur ou autorité)
+    pub fn supersede_document(
+        env: Env,
+        authorized_org: Address,
+        uid: String,
+        document: DocumentInput,
+        attestation: Option<StaffAttestation>
+    ) -> Result<u32, Error> {
+        authorized_org.require_auth();
+        Self::ensure_not_paused(&env)?;
+        
+        Self::validate_document(&document)?;
+        
+        let mut part = Self::load_part(&env, &uid)?;
+        
+        // Une autre organisation ne peut pas publier la version en vigueur d'un document qu'elle n'a pas émis
+        let latest = Self::load_latest_document(&env, &uid, &document.name)?;
+        if Self::ensure_is_admin(&env, &authorized_org).is_err()
+            && Self::ensure_has_role(&env, &authorized_org, OrgType::Authority).is_err() {
+            if latest.uploader != authorized_org {
+                return Err(Error::NotAuthorized);
+            }
+            Self::ensure_can_add_document(&env, &authorized_org, &uid)?;
+        }
+        
+        let attestation = Self::verify_attestation(&env, &authorized_org, &uid, &document.digest, attestation)?;
+        let id = Self::append_document(&env, &mut part, document, attestation, &authorized_org);
+        Self::save_part(&env, &part);
+        
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(id)
+    }
+    
+    // Révoquer un document émis par erreur (auteur du document, administrateur ou autorité) ;
+    // le document reste en place, marqué révoqué. Si quarantine_part est vrai et que le document
+    // est le certificat de remise en service en vigueur, la pièce passe en quarantaine
+    // (mesure corrective : disponible en pause pour les administrateurs et autorités)
+    pub fn revoke_document(
+        env: Env,
+        caller: Address,
+        uid: String,
+        document_id: u32,
+        reason_hash: BytesN<32>,
+        quarantine_part: bool
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        Self::ensure_schema_current(&env)?;
+        
+        let mut part = Self::load_part(&env, &uid)?;
+        let key = DataKey::Document(uid.clone(), document_id);
+        let mut record: DocumentRecord = env.storage().persistent().get(&key).ok_or(Error::DocumentNotFound)?;
+        
+        // Administrateurs et autorités révoquent même en pause ; l'auteur doit être une organisation active
+        // et reste soumis au coupe-circuit
+        if Self::ensure_is_admin(&env, &caller).is_err()
+            && Self::ensure_has_role(&env, &caller, OrgType::Authority).is_err() {
+            if record.uploader != caller || !Self::load_org(&env, &caller).is_some_and(|org| org.active) {
+                return Err(Error::NotAuthorized);
+            }
+            Self::ensure_not_paused(&env)?;
+        }
+        if record.revoked {
+            return Err(Error::DocumentAlreadyRevoked);
+        }
+        Self::ensure_valid_digest(&reason_hash)?;
+        
+        record.revoked = true;
+        record.revocation_reason = Some(reason_hash.clone());
+        env.storage().persistent().set(&key, &record);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        Self::record_history(&env, &uid, &caller, HistoryEvent::DocumentRevoked(document_id, reason_hash.clone()));
+        
+        log!(&env, "Revoked document: {} of part: {} by: {}", document_id, uid, caller);
+        env.events().publish((EVT_DOCUMENT_REVOKED, uid.clone()), (caller.clone(), document_id, reason_hash));
+        
+        // Sans certificat de remise en service valide, la pièce n'est plus navigable ;
+        // pendant un ordre de travail, elle reste en maintenance mais ne pourra en sortir
+        // en Active que via release_from_quarantine
+        if quarantine_part
+            && Self::is_release_document(record.doc_type)
+            && Self::load_latest_document(&env, &uid, &record.name).is_ok_and(|latest| latest.id == document_id)
+            && (part.status == PartStatus::Active || part.status == PartStatus::InMaintenance) {
+            if Self::has_open_work_order(&env, &uid) {
+                Self::set_quarantine_hold(&env, &uid);
+            } else {
+                Self::change_status(&env, &mut part, PartStatus::Quarantined, &caller);
+            }
+        }
+        
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        Ok(())
+    }
+    
+    // Obtenir un document par son identifiant
+    pub fn get_document(env: Env, uid: String, document_id: u32) -> Result<DocumentRecord, Error> {
+        env.storage().persistent().get(&DataKey::Document(uid, document_id)).ok_or(Error::DocumentNotFound)
+    }
+    
+    // Obtenir la version en vigueur d'un document
+    pub fn get_latest_document(env: Env, uid: String, document_name: String) -> Result<DocumentRecord, Error> {
+        Self::load_latest_document(&env, &uid, &document_name)
+    }
+    
+    // Obtenir les versions d'un document, de la plus ancienne à la plus récente, page par page
+    // (la position i correspond à la version i + 1)
+    pub fn get_document_versions(
+        env: Env,
+        uid: String,
+        document_name: String,
+        start_after: Option<u32>,
+        limit: u32
+    ) -> Result<DocumentPage, Error> {
+        // Chaque version coûte deux lectures (index de version puis document)
+        let latest = Self::load_latest_document(&env, &uid, &document_name)?;
+        let (start, end) = Self::page_bounds(start_after, limit.min(MAX_JOINED_PAGE_SIZE), latest.version)?;
+        
+        let mut documents = Vec::new(&env);
+        for i in start..end {
+            let id: Option<u32> = env.storage().persistent()
+                .get(&DataKey::DocumentVersion(uid.clone(), document_name.clone(), i + 1));
+            if let Some(record) = id.and_then(|id| env.storage().persistent().get(&DataKey::Document(uid.clone(), id))) {
+                documents.push_back(record);
+            }
+        }
+        
+        let next_cursor = if end < latest.version { Some(end - 1) } else { None };
+        Ok(DocumentPage { documents, next_cursor })
+    }
+    
+    // Vérifier qu'une empreinte de document est enregistrée pour une pièce (ouvert à tous, ex : inspecteur)
+    pub fn verify_document(env: Env, uid: String, digest: BytesN<32>) -> DocumentVerification {
+        let id: Option<u32> = env.storage().persistent().get(&DataKey::DocumentByDigest(uid.clone(), digest));
+        let record: Option<DocumentRecord> = id.and_then(|id| env.storage().persistent().get(&DataKey::Document(uid, id)));
+        
+        match record {
+            Some(record) => DocumentVerification {
+                registered: true,
+                name: Some(record.name),
+                version: Some(record.version),
+                uploader: Some(record.uploader),
+                timestamp: Some(record.timestamp),
+                revoked: record.revoked,
+            },
+            None => DocumentVerification {
+                registered: false,
+                name: None,
+                version: None,
+                uploader: None,
+                timestamp: None,
+                revoked: false,
+            },
+        }
+    }
+    
+    // Obtenir tous les documents d'une pièce (toutes versions), page par page
+    pub fn get_documents(
+        env: Env,
+        uid: String,
+        start_after: Option<u32>,
+        limit: u32
+    ) -> Result<DocumentPage, Error> {
+        // Vérifier que la pièce existe
+        Self::load_part(&env, &uid)?;
+        
+        let len: u32 = env.storage().persistent().get(&DataKey::DocumentLen(uid.clone())).unwrap_or(0);
+        let (start, end) = Self::page_bounds(start_after, limit, len)?;
+        
+        let mut documents = Vec::new(&env);
+        for i in start..end {
+            if let Some(record) = env.storage().persistent().get(&DataKey::Document(uid.clone(), i)) {
+                documents.push_back(record);
+            }
+        }
+        
+        let next_cursor = if end < len { Some(end - 1) } else { None };
+        Ok(DocumentPage { documents, next_cursor })
+    }
+    
+    // Fonctions d'aide privées
+    
+    // Ajouter un administrateur (sans contrôle d'autorisation)
+    fn apply_add_admin(env: &Env, caller: &Address, new_admin: Address) -> Result<(), Error> {
+        let mut admins: Vec<Address> = env.storage().instance().get(&ADMINS).unwrap_or(Vec::new(env));
+        if admins.contains(&new_admin) {
+            return Err(Error::InvalidInput);
+        }
+        
+        admins.push_back(new_admin.clone());
+        env.storage().instance().set(&ADMINS, &admins);
+        
+        log!(env, "Admin {} added admin: {}", caller, new_admin);
+        env.events().publish((EVT_ADMIN_ADDED, new_admin), caller);
+        Ok(())
+    }
+    
+    // Retirer un administrateur (sans contrôle d'autorisation)
+    fn apply_remove_admin(env: &Env, caller: &Address, admin: Address) -> Result<(), Error> {
+        let mut admins: Vec<Address> = env.storage().instance().get(&ADMINS).unwrap_or(Vec::new(env));
+        let pos = match admins.first_index_of(&admin) {
+            Some(pos) => pos,
+            None => return Err(Error::NotAuthorized),
+        };
+        if admins.len() == 1 {
+            return Err(Error::LastAdmin);
+        }
+        
+        // Le seuil de signatures doit rester atteignable
+        if admins.len() - 1 < Self::threshold(env) {
+            return Err(Error::InvalidInput);
+        }
+        
+        admins.remove(pos);
+        env.storage().instance().set(&ADMINS, &admins);
+        
+        log!(env, "Admin {} removed admin: {}", caller, admin);
+        env.events().publish((EVT_ADMIN_REMOVED, admin), caller);
+        Ok(())
+    }
+    
+    // Remplacer un administrateur par une autre adresse, à la même position (sans contrôle d'autorisation)
+    fn apply_transfer_admin(env: &Env, admin: Address, new_admin: Address) -> Result<(), Error> {
+        let mut admins: Vec<Address> = env.storage().instance().get(&ADMINS).unwrap_or(Vec::new(env));
+        let pos = match admins.first_index_of(&admin) {
+            Some(pos) => pos,
+            None => return Err(Error::NotAuthorized),
+        };
+        if admins.contains(&new_admin) {
+            return Err(Error::InvalidInput);
+        }
+        
+        admins.set(pos, new_admin.clone());
+        env.storage().instance().set(&ADMINS, &admins);
+        
+        log!(env, "Admin {} transferred their role to: {}", admin, new_admin);
+        env.events().publish((EVT_ADMIN_TRANSFERRED, new_admin), admin);
+        Ok(())
+    }
+    
+    // Enregistrer une organisation après vérification des doublons
+    fn apply_register_organization(
+        env: &Env,
+        caller: &Address,
+        org_address: Address,
+        name: String,
+        org_type: OrgType,
+        certificates: Vec<String>
+    ) -> Result<(), Error> {
+        Self::ensure_not_paused(env)?;
+        
+        // Une adresse ne peut être enregistrée qu'une fois (voir add_org_role pour les agréments multiples)
+        if Self::load_org(env, &org_address).is_some() {
+            return Err(Error::OrgAlreadyRegistered);
+        }
+        
+        Self::store_organization(env, caller, org_address, name, org_type, certificates);
+        Ok(())
+    }
+    
+    // Suspendre une organisation
+    fn apply_suspend_organization(
+        env: &Env,
+        caller: &Address,
+        org_address: Address,
+        reason: String
+    ) -> Result<(), Error> {
+        Self::ensure_schema_current(env)?;
+        
+        let mut org = Self::load_org(env, &org_address).ok_or(Error::OrgNotRegistered)?;
+        if !org.active {
+            return Err(Error::InvalidInput);
+        }
+        
+        org.active = false;
+        Self::save_org(env, &org);
+        Self::record_org_history(env, &org_address, OrgAction::Suspended, caller, &reason);
+        
+        log!(env, "Suspended organization: {} by: {}", org_address, caller);
+        env.events().publish((EVT_ORG_SUSPENDED, org_address), (caller, reason));
+        Ok(())
+    }
+    
+    // Réactiver une organisation
+    fn apply_reactivate_organization(
+        env: &Env,
+        caller: &Address,
+        org_address: Address,
+        reason: String
+    ) -> Result<(), Error> {
+        Self::ensure_not_paused(env)?;
+        
+        let mut org = Self::load_org(env, &org_address).ok_or(Error::OrgNotRegistered)?;
+        if org.active {
+            return Err(Error::InvalidInput);
+        }
+        
+        org.active = true;
+        Self::save_org(env, &org);
+        Self::record_org_history(env, &org_address, OrgAction::Reactivated, caller, &reason);
+        
+        log!(env, "Reactivated organization: {} by: {}", org_address, caller);
+        env.events().publish((EVT_ORG_REACTIVATED, org_address), (caller, reason));
+        Ok(())
+    }
+    
+    // Mettre à jour une organisation
+    fn apply_update_organization(
+        env: &Env,
+        caller: &Address,
+        org_address: Address,
+        name: String,
+        certificates: Vec<String>,
+        reason: String
+    ) -> Result<(), Error> {
+        Self::ensure_not_paused(env)?;
+        
+        let mut org = Self::load_org(env, &org_address).ok_or(Error::OrgNotRegistered)?;
+        org.name = name.clone();
+        org.certificates = certificates.clone();
+        Self::save_org(env, &org);
+        Self::record_org_history(env, &org_address, OrgAction::Updated, caller, &reason);
+        
+        log!(env, "Updated organization: {} by: {}", org_address, caller);
+        env.events().publish((EVT_ORG_UPDATED, org_address), (caller, reason, name, certificates));
+        Ok(())
+    }
+    
+    // Retirer une organisation de l'annuaire
+    fn apply_deregister_organization(
+        env: &Env,
+        caller: &Address,
+        org_address: Address,
+        reason: String
+    ) -> Result<(), Error> {
+        Self::ensure_schema_current(env)?;
+        
+        let org = Self::load_org(env, &org_address).ok_or(Error::OrgNotRegistered)?;
+        
+        env.storage().persistent().remove(&DataKey::Org(org_address.clone()));
+        Self::count_role(env, org.org_type, false);
+        for role in org.extra_roles.iter() {
+            Self::count_role(env, role, false);
+        }
+        
+        // Retirer l'adresse de l'annuaire
+        Self::org_directory_remove(env, &org_address);
+        
+        Self::record_org_history(env, &org_address, OrgAction::Deregistered, caller, &reason);
+        
+        log!(env, "Deregistered organization: {} by: {}", org_address, caller);
+        env.events().publish((EVT_ORG_DEREGISTERED, org_address), (caller, reason));
+        Ok(())
+    }
+    
+    // Ajouter un agrément à une organisation
+    fn apply_add_org_role(
+        env: &Env,
+        caller: &Address,
+        org_address: Address,
+        role: OrgType,
+        reason: String
+    ) -> Result<(), Error> {
+        Self::ensure_not_paused(env)?;
+        
+        let mut org = Self::load_org(env, &org_address).ok_or(Error::OrgNotRegistered)?;
+        if org.has_role(role) {
+            return Err(Error::InvalidInput);
+        }
+        
+        org.extra_roles.push_back(role);
+        Self::save_org(env, &org);
+        Self::count_role(env, role, true);
+        Self::record_org_history(env, &org_address, OrgAction::RoleAdded(role), caller, &reason);
+        
+        log!(env, "Added role {:?} to organization: {} by: {}", role, org_address, caller);
+        env.events().publish((EVT_ROLE_ADDED, org_address), (caller, role, reason));
+        Ok(())
+    }
+    
+    // Retirer un agrément d'une organisation
+    fn apply_remove_org_role(
+        env: &Env,
+        caller: &Address,
+        org_address: Address,
+        role: OrgType,
+        reason: String
+    ) -> Result<(), Error> {
+        Self::ensure_schema_current(env)?;
+        
+        let mut org = Self::load_org(env, &org_address).ok_or(Error::OrgNotRegistered)?;
+        let pos = match org.extra_roles.first_index_of(role) {
+            Some(pos) => pos,
+            None => return Err(Error::InvalidInput),
+        };
+        
+        org.extra_roles.remove(pos);
+        Self::save_org(env, &org);
+        Self::count_role(env, role, false);
+        Self::record_org_history(env, &org_address, OrgAction::RoleRemoved(role), caller, &reason);
+        
+        log!(env, "Removed role {:?} from organization: {} by: {}", role, org_address, caller);
+        env.events().publish((EVT_ROLE_REMOVED, org_address), (caller, role, reason));
+        Ok(())
+    }
+    
+    // Modifier le seuil de signatures (entre 1 et le nombre d'administrateurs)
+    fn apply_set_threshold(env: &Env, caller: &Address, threshold: u32) -> Result<(), Error> {
+        let admins: Vec<Address> = env.storage().instance().get(&ADMINS).unwrap_or(Vec::new(env));
+        if threshold == 0 || threshold > admins.len() {
+            return Err(Error::InvalidInput);
+        }
+        
+        env.storage().instance().set(&THRESHOLD, &threshold);
+        
+        log!(env, "Admin {} set approval threshold to: {}", caller, threshold);
+        env.events().publish((EVT_THRESHOLD_SET,), (caller, threshold));
+        Ok(())
+    }
+    
+    // Lever la pause d'urgence
+    fn apply_unpause(env: &Env, caller: &Address) -> Result<(), Error> {
+        if !Self::paused(env) {
+            return Err(Error::InvalidInput);
+        }
+        
+        env.storage().instance().set(&PAUSED, &false);
+        
+        log!(env, "Contract unpaused by admin: {}", caller);
+        env.events().publish((EVT_UNPAUSED,), caller.clone());
+        Ok(())
+    }
+    
+    // Le contrat est-il en pause d'urgence ?
+    fn paused(env: &Env) -> bool {
+        env.storage().instance().get(&PAUSED).unwrap_or(false)
+    }
+    
+    // Refuser les écritures pendant une pause d'urgence ou une migration inachevée
+    fn ensure_not_paused(env: &Env) -> Result<(), Error> {
+        if Self::paused(env) {
+            return Err(Error::ContractPaused);
+        }
+        Self::ensure_schema_current(env)
+    }
+    
+    // Refuser les écritures tant que le stockage n'est pas au schéma attendu par le code
+    // (entre upgrade et la fin de migrate, une écriture pourrait entrer en conflit avec les données à migrer)
+    fn ensure_schema_current(env: &Env) -> Result<(), Error> {
+        if Self::get_schema_version(env.clone()) < CURRENT_SCHEMA_VERSION {
+            return Err(Error::MigrationPending);
+        }
+        Ok(())
+    }
+    
+    // Remplacer le WASM du contrat
+    fn apply_upgrade(env: &Env, caller: &Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+        
+        log!(env, "Admin {} upgraded contract to: {}", caller, new_wasm_hash);
+        env.events().publish((EVT_UPGRADED,), (caller, new_wasm_hash));
+        Ok(())
+    }
+    
+    // Un lot de la migration 0 -> 1 : les organisations au premier appel, puis les pièces par lots
+    // (les pièces migrées sont retirées de l'ancienne map, qui sert de curseur) ; vrai une fois terminé
+    fn migrate_v0_to_v1(env: &Env, limit: u32) -> bool {
+        let instance = env.storage().instance();
+        if instance.has(&LEGACY_OEM_ORGS) || instance.has(&LEGACY_MRO_ORGS) {
+            Self::migrate_v0_organizations(env);
+            return false;
+        }
+        
+        // Pièces : une entrée persistante chacune, index, historique et documents reconstruits
+        let mut legacy_parts: Map<String, LegacyAeronauticPart> = instance.get(&LEGACY_PARTS).unwrap_or(Map::new(env));
+        let batch = legacy_parts.keys().slice(0..legacy_parts.len().min(limit));
+        for uid in batch.iter() {
+            if let Some(legacy) = legacy_parts.get(uid.clone()) {
+                legacy_parts.remove(uid.clone());
+                Self::migrate_v0_part(env, uid, legacy);
+            }
+        }
+        
+        if legacy_parts.is_empty() {
+            instance.remove(&LEGACY_PARTS);
+            true
+        } else {
+            instance.set(&LEGACY_PARTS, &legacy_parts);
+            false
+        }
+    }
+    
+    // Migrer l'annuaire des organisations du schéma version 0
+    fn migrate_v0_organizations(env: &Env) {
+        // OEM puis MRO ; une adresse présente dans les deux listes reçoit un agrément supplémentaire
+        for key in [LEGACY_OEM_ORGS, LEGACY_MRO_ORGS] {
+            let legacy_orgs: Vec<LegacyOrganization> = env.storage().instance().get(&key).unwrap_or(Vec::new(env));
+            for legacy in legacy_orgs.iter() {
+                match Self::load_org(env, &legacy.id) {
+                    Some(mut org) => {
+                        if !org.has_role(legacy.org_type) {
+                            org.extra_roles.push_back(legacy.org_type);
+                            Self::save_org(env, &org);
+                            Self::count_role(env, legacy.org_type, true);
+                        }
+                    },
+                    None => {
+                        let org = Organization {
+                            id: legacy.id.clone(),
+                            name: legacy.name,
+                            org_type: legacy.org_type,
+                            extra_roles: Vec::new(env),
+                            certificates: legacy.certificates,
+                            active: legacy.active,
+                        };
+                        Self::save_org(env, &org);
+                        Self::count_role(env, legacy.org_type, true);
+                        Self::org_directory_push(env, &legacy.id);
+                    },
+                }
+            }
+            env.storage().instance().remove(&key);
+        }
+    }
+    
+    // Migrer une pièce du schéma version 0 (les écritures étant bloquées, l'UID est encore libre)
+    fn migrate_v0_part(env: &Env, uid: String, legacy: LegacyAeronauticPart) {
+        let mut part = AeronauticPart {
+            uid: uid.clone(),
+            part_number: legacy.part_number,
+            serial_number: legacy.serial_number,
+            manufacturer: legacy.manufacturer.clone(),
+            date_of_manufacture: legacy.date_of_manufacture,
+            current_owner: legacy.current_owner,
+            status: legacy.status,
+            total_hours: legacy.total_hours,
+            total_cycles: legacy.total_cycles,
+            last_updated: legacy.last_updated,
+        };
+        
+        // L'ancien schéma ne conservait pas d'historique : la trace commence à la migration
+        Self::record_history(env, &uid, &part.manufacturer, HistoryEvent::Created);
+        
+        // Chaque document devient la version 1 d'un DocumentRecord, attribuée au fabricant
+        let manufacturer = legacy.manufacturer;
+        for (name, hash) in legacy.document_hashes.iter() {
+            let document = DocumentInput {
+                name,
+                doc_type: DocumentType::Legacy,
+                hash_algorithm: HashAlgorithm::Sha256,
+                digest: Self::legacy_digest(env, &hash),
+                uri: None,
+            };
+            Self::append_document(env, &mut part, document, None, &manufacturer);
+        }
+        part.last_updated = legacy.last_updated;
+        
+        Self::save_part(env, &part);
+        Self::index_push(env, IndexKey::All, &uid);
+        Self::index_push(env, IndexKey::Manufacturer(part.manufacturer.clone()), &uid);
+        Self::index_push(env, IndexKey::Owner(part.current_owner.clone()), &uid);
+        Self::index_push(env, IndexKey::Status(part.status), &uid);
+        Self::index_push(env, IndexKey::OwnerStatus(part.current_owner.clone(), part.status), &uid);
+    }
+    
+    // Seuil de signatures requis pour les actions d'administration (1 par défaut)
+    fn threshold(env: &Env) -> u32 {
+        env.storage().instance().get(&THRESHOLD).unwrap_or(1)
+    }
+    
+    // Vérifier qu'un administrateur peut agir seul (seuil de signatures à 1)
+    fn ensure_direct_admin(env: &Env, address: &Address) -> Result<(), Error> {
+        Self::ensure_is_admin(env, address)?;
+        
+        if Self::threshold(env) > 1 {
+            return Err(Error::MultisigRequired);
+        }
+        
+        Ok(())
+    }
+    
+    // Exécuter l'action d'une proposition approuvée
+    fn execute_action(env: &Env, proposer: &Address, action: ProposalAction) -> Result<(), Error> {
+        match action {
+            ProposalAction::RegisterOrg(org_address, name, org_type, certificates) => {
+                Self::apply_register_organization(env, proposer, org_address, name, org_type, certificates)
+            },
+            ProposalAction::SuspendOrg(org_address, reason) => {
+                Self::apply_suspend_organization(env, proposer, org_address, reason)
+            },
+            ProposalAction::ReactivateOrg(org_address, reason) => {
+                Self::apply_reactivate_organization(env, proposer, org_address, reason)
+            },
+            ProposalAction::UpdateOrg(org_address, name, certificates, reason) => {
+                Self::apply_update_organization(env, proposer, org_address, name, certificates, reason)
+            },
+            ProposalAction::DeregisterOrg(org_address, reason) => {
+                Self::apply_deregister_organization(env, proposer, org_address, reason)
+            },
+            ProposalAction::AddOrgRole(org_address, role, reason) => {
+                Self::apply_add_org_role(env, proposer, org_address, role, reason)
+            },
+            ProposalAction::RemoveOrgRole(org_address, role, reason) => {
+                Self::apply_remove_org_role(env, proposer, org_address, role, reason)
+            },
+            ProposalAction::AddAdmin(new_admin) => Self::apply_add_admin(env, proposer, new_admin),
+            ProposalAction::RemoveAdmin(admin) => Self::apply_remove_admin(env, proposer, admin),
+            ProposalAction::TransferAdmin(admin, new_admin) => Self::apply_transfer_admin(env, admin, new_admin),
+            ProposalAction::SetThreshold(threshold) => Self::apply_set_threshold(env, proposer, threshold),
+            ProposalAction::Upgrade(new_wasm_hash) => Self::apply_upgrade(env, proposer, new_wasm_hash),
+            ProposalAction::Unpause => Self::apply_unpause(env, proposer),
+        }
+    }
+    
+    // Compter les approbations d'administrateurs encore en poste
+    fn count_approvals(env: &Env, proposal: &Proposal) -> u32 {
+        let admins: Vec<Address> = env.storage().instance().get(&ADMINS).unwrap_or(Vec::new(env));
+        proposal.approvals.iter().filter(|a| admins.contains(a)).count() as u32
+    }
+    
+    // Enregistrer une proposition dans sa propre entrée persistante
+    fn save_proposal(env: &Env, proposal: &Proposal) {
+        let key = DataKey::Proposal(proposal.id);
+        env.storage().persistent().set(&key, proposal);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // Exécuter une proposition si elle a atteint le seuil de signatures
+    fn try_execute(env: &Env, mut proposal: Proposal) -> Result<bool, Error> {
+        if Self::count_approvals(env, &proposal) < Self::threshold(env) {
+            Self::save_proposal(env, &proposal);
+            return Ok(false);
+        }
+        
+        proposal.executed = true;
+        Self::save_proposal(env, &proposal);
+        Self::execute_action(env, &proposal.proposer, proposal.action.clone())?;
+        
+        log!(env, "Executed proposal: {}", proposal.id);
+        env.events().publish((EVT_PROPOSAL_EXECUTED, proposal.id), proposal.proposer);
+        Ok(true)
+    }
+    
+    // Vérifier si une adresse est un administrateur
+    fn ensure_is_admin(env: &Env, address: &Address) -> Result<(), Error> {
+        let admins: Vec<Address> = env.storage().instance().get(&ADMINS).unwrap_or(Vec::new(env));
+        
+        for admin in admins.iter() {
+            if &admin == address {
+                return Ok(());
+            }
+        }
+        
+        Err(Error::NotAuthorized)
+    }
+    
+    // Enregistrer une organisation dans l'annuaire
+    fn store_organization(
+        env: &Env,
+        caller: &Address,
+        org_address: Address,
+        name: String,
+        org_type: OrgType,
+        certificates: Vec<String>
+    ) {
+        // Créer l'organisation
+        let org = Organization {
+            id: org_address.clone(),
+            name,
+            org_type,
+            extra_roles: Vec::new(env),
+            certificates,
+            active: true,
+        };
+        
+        Self::save_org(env, &org);
+        Self::count_role(env, org_type, true);
+        
+        // Ajouter l'adresse à l'annuaire
+        Self::org_directory_push(env, &org_address);
+        
+        log!(env, "Registered new organization: {} ({:?})", org_address, org_type);
+        env.events().publish((EVT_ORG_REGISTERED, org_address), (org_type, caller.clone()));
+    }
+    
+    // Charger une organisation depuis l'annuaire
+    fn load_org(env: &Env, address: &Address) -> Option<Organization> {
+        let key = DataKey::Org(address.clone());
+        let org: Option<Organization> = env.storage().persistent().get(&key);
+        if org.is_some() {
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
+        org
+    }
+    
+    // Enregistrer une organisation dans sa propre entrée persistante
+    fn save_org(env: &Env, org: &Organization) {
+        let key = DataKey::Org(org.id.clone());
+        env.storage().persistent().set(&key, org);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // En-tête de l'annuaire (vide s'il n'a jamais été écrit)
+    fn org_directory(env: &Env) -> OrgDirectory {
+        env.storage().persistent()
+            .get(&DataKey::OrgDirectory)
+            .unwrap_or(OrgDirectory { len: 0, head: None, tail: None })
+    }
+    
+    fn load_org_node(env: &Env, address: &Address) -> Option<OrgNode> {
+        env.storage().persistent().get(&DataKey::OrgNode(address.clone()))
+    }
+    
+    fn save_org_node(env: &Env, address: &Address, node: &OrgNode) {
+        let key = DataKey::OrgNode(address.clone());
+        env.storage().persistent().set(&key, node);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    fn save_org_directory(env: &Env, directory: &OrgDirectory) {
+        env.storage().persistent().set(&DataKey::OrgDirectory, directory);
+        env.storage().persistent().extend_ttl(&DataKey::OrgDirectory, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // Ajouter une adresse à la fin de l'annuaire
+    fn org_directory_push(env: &Env, address: &Address) {
+        if Self::load_org_node(env, address).is_some() {
+            return;
+        }
+        
+        let mut directory = Self::org_directory(env);
+        match &directory.tail {
+            Some(tail) => {
+                if let Some(mut tail_node) = Self::load_org_node(env, tail) {
+                    tail_node.next = Some(address.clone());
+                    Self::save_org_node(env, tail, &tail_node);
+                }
+            },
+            None => directory.head = Some(address.clone()),
+        }
+        Self::save_org_node(env, address, &OrgNode { prev: directory.tail.clone(), next: None });
+        
+        directory.tail = Some(address.clone());
+        directory.len += 1;
+        Self::save_org_directory(env, &directory);
+    }
+    
+    // Retirer une adresse de l'annuaire en raccordant ses voisins ; l'ordre des autres organisations est conservé
+    fn org_directory_remove(env: &Env, address: &Address) {
+        let node = match Self::load_org_node(env, address) {
+            Some(node) => node,
+            None => return,
+        };
+        
+        let mut directory = Self::org_directory(env);
+        match &node.prev {
+            Some(prev) => {
+                if let Some(mut prev_node) = Self::load_org_node(env, prev) {
+                    prev_node.next = node.next.clone();
+                    Self::save_org_node(env, prev, &prev_node);
+                }
+            },
+            None => directory.head = node.next.clone(),
+        }
+        match &node.next {
+            Some(next) => {
+                if let Some(mut next_node) = Self::load_org_node(env, next) {
+                    next_node.prev = node.prev.clone();
+                    Self::save_org_node(env, next, &next_node);
+                }
+            },
+            None => directory.tail = node.prev.clone(),
+        }
+        env.storage().persistent().remove(&DataKey::OrgNode(address.clone()));
+        
+        directory.len -= 1;
+        Self::save_org_directory(env, &directory);
+    }
+    
+    // Nombre d'organisations enregistrées qui détiennent un rôle (type principal ou agrément)
+    fn role_count(env: &Env, role: OrgType) -> u32 {
+        env.storage().persistent().get(&DataKey::RoleCount(role)).unwrap_or(0)
+    }
+    
+    // Tenir à jour le compteur d'un rôle lors d'un enregistrement, d'un retrait ou d'un changement d'agrément
+    fn count_role(env: &Env, role: OrgType, added: bool) {
+        let count = Self::role_count(env, role);
+        let count = if added { count + 1 } else { count.saturating_sub(1) };
+        let key = DataKey::RoleCount(role);
+        env.storage().persistent().set(&key, &count);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // Ajouter une entrée à l'historique d'une organisation
+    fn record_org_history(env: &Env, org_address: &Address, action: OrgAction, actor: &Address, reason: &String) {
+        let len_key = DataKey::OrgHistoryLen(org_address.clone());
+        let seq: u32 = env.storage().persistent().get(&len_key).unwrap_or(0);
+        let record_key = DataKey::OrgHistory(org_address.clone(), seq);
+        
+        let record = OrgRecord {
+            seq,
+            action,
+            actor: actor.clone(),
+            reason: reason.clone(),
+            timestamp: env.ledger().timestamp(),
+        };
+        
+        env.storage().persistent().set(&record_key, &record);
+        env.storage().persistent().set(&len_key, &(seq + 1));
+        env.storage().persistent().extend_ttl(&record_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage().persistent().extend_ttl(&len_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // Vérifier qu'une adresse est une organisation active du type demandé
+    fn ensure_has_role(env: &Env, address: &Address, org_type: OrgType) -> Result<(), Error> {
+        match Self::load_org(env, address) {
+            Some(org) if org.active && org.has_role(org_type) => Ok(()),
+            _ => Err(Error::OrgNotRegistered),
+        }
+    }
+    
+    // Vérifier si une adresse est un OEM enregistré
+    fn ensure_is_oem(env: &Env, address: &Address) -> Result<(), Error> {
+        Self::ensure_has_role(env, address, OrgType::OEM).map_err(|_| Error::NotAnOEM)
+    }
+    
+    // Vérifier si une adresse est un MRO, une autorité ou le propriétaire d'une pièce
+    fn ensure_can_update_status(env: &Env, address: &Address, part_uid: &String) -> Result<(), Error> {
+        // Si ce n'est ni un MRO ni une autorité, vérifier si c'est le propriétaire
+        if Self::ensure_is_mro_or_authority(env, address).is_err() {
+            let part = Self::load_part(env, part_uid)?;
+            if &part.current_owner != address {
+                return Err(Error::NotAuthorized);
+            }
+        }
+        
+        Ok(())
+    }
+    
+    // Vérifier si une adresse est un MRO ou une autorité de navigabilité
+    fn ensure_is_mro_or_authority(env: &Env, address: &Address) -> Result<(), Error> {
+        if Self::ensure_is_mro(env, address).is_ok()
+            || Self::ensure_has_role(env, address, OrgType::Authority).is_ok() {
+            return Ok(());
+        }
+        Err(Error::NotAuthorized)
+    }
+    
+    // Vérifier qu'un changement de statut respecte le cycle de vie d'une pièce :
+    // - Retired est terminal (plus aucune mise à jour)
+    // - une pièce en quarantaine ne repart en maintenance que par un MRO ou une autorité,
+    //   et ne redevient Active que via release_from_quarantine, y compris après
+    //   un passage en maintenance
+    // - une pièce ayant atteint une limite de vie ne peut plus qu'être retirée
+    fn ensure_status_transition(
+        env: &Env,
+        part: &AeronauticPart,
+        actor: &Address,
+        to: PartStatus
+    ) -> Result<(), Error> {
+        let uid = &part.uid;
+        match (part.status, to) {
+            (PartStatus::Retired, _) => Err(Error::InvalidStatusTransition),
+            (_, PartStatus::Retired) => Self::ensure_can_ground(env, part, actor),
+            _ if Self::life_limit_reached(env, part) => Err(Error::LifeLimitExceeded),
+            (_, PartStatus::Quarantined) => Self::ensure_can_ground(env, part, actor),
+            (PartStatus::Quarantined, PartStatus::Active) => Err(Error::ReleaseDocumentRequired),
+            (PartStatus::Quarantined, PartStatus::InMaintenance) => Self::ensure_is_mro_or_authority(env, actor),
+            (PartStatus::InMaintenance, PartStatus::Active) if Self::quarantine_hold(env, uid) => {
+                Err(Error::ReleaseDocumentRequired)
+            },
+            _ => Ok(()),
+        }
+    }
+    
+    // Vérifier qu'une adresse a la garde de la pièce : son propriétaire ou le MRO qui l'a en maintenance
+    fn ensure_custodian(env: &Env, part: &AeronauticPart, actor: &Address) -> Result<(), Error> {
+        if &part.current_owner == actor || Self::assigned_mro(env, &part.uid).as_ref() == Some(actor) {
+            return Ok(());
+        }
+        Err(Error::NotAuthorized)
+    }
+    
+    // Retrait et quarantaine : gardien de la pièce ou autorité, jamais un MRO tiers
+    fn ensure_can_ground(env: &Env, part: &AeronauticPart, actor: &Address) -> Result<(), Error> {
+        if Self::ensure_custodian(env, part, actor).is_ok()
+            || Self::ensure_has_role(env, actor, OrgType::Authority).is_ok() {
+            return Ok(());
+        }
+        Err(Error::NotAuthorized)
+    }
+    
+    // La maintenance en cours a commencé depuis la quarantaine
+    fn quarantine_hold(env: &Env, uid: &String) -> bool {
+        env.storage().persistent().has(&DataKey::QuarantineHold(uid.clone()))
+    }
+    
+    // Déplacer une pièce entre les index de statut ; une entrée en maintenance par un MRO
+    // lui affecte la pièce, une sortie de maintenance clôt l'affectation
+    fn reindex_status(
+        env: &Env,
+        uid: &String,
+        owner: &Address,
+        from: PartStatus,
+        to: PartStatus,
+        actor: &Address
+    ) {
+        Self::index_remove(env, IndexKey::Status(from), uid);
+        Self::index_remove(env, IndexKey::OwnerStatus(owner.clone(), from), uid);
+        Self::index_push(env, IndexKey::Status(to), uid);
+        Self::index_push(env, IndexKey::OwnerStatus(owner.clone(), to), uid);
+        
+        if from == PartStatus::InMaintenance {
+            Self::release_maintainer(env, uid);
+        }
+        if to == PartStatus::InMaintenance && Self::ensure_is_mro(env, actor).is_ok() {
+            Self::assign_maintainer(env, uid, actor);
+        }
+        
+        // Une maintenance ouverte depuis la quarantaine ne se termine en Active que par
+        // release_from_quarantine ; toute autre sortie de maintenance lève la marque
+        if from == PartStatus::Quarantined && to == PartStatus::InMaintenance {
+            Self::set_quarantine_hold(env, uid);
+        } else if to != PartStatus::InMaintenance {
+            env.storage().persistent().remove(&DataKey::QuarantineHold(uid.clone()));
+        }
+    }
+    
+    // Marquer la maintenance en cours comme soumise à une remise en service
+    fn set_quarantine_hold(env: &Env, uid: &String) {
+        let key = DataKey::QuarantineHold(uid.clone());
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // MRO qui a la pièce en maintenance, le cas échéant
+    fn assigned_mro(env: &Env, uid: &String) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::AssignedMro(uid.clone()))
+    }
+    
+    // Affecter une pièce en maintenance à un MRO
+    fn assign_maintainer(env: &Env, uid: &String, mro: &Address) {
+        let key = DataKey::AssignedMro(uid.clone());
+        env.storage().persistent().set(&key, mro);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        Self::index_push(env, IndexKey::Maintainer(mro.clone()), uid);
+    }
+    
+    // Clore l'affectation de maintenance d'une pièce
+    fn release_maintainer(env: &Env, uid: &String) {
+        if let Some(mro) = Self::assigned_mro(env, uid) {
+            Self::index_remove(env, IndexKey::Maintainer(mro), uid);
+            env.storage().persistent().remove(&DataKey::AssignedMro(uid.clone()));
+        }
+    }
+    
+    // Pendant une maintenance affectée, seul le MRO affecté peut agir sur le statut de la pièce
+    fn ensure_maintenance_holder(env: &Env, uid: &String, actor: &Address) -> Result<(), Error> {
+        match Self::assigned_mro(env, uid) {
+            Some(mro) if &mro != actor => Err(Error::NotAuthorized),
+            _ => Ok(()),
+        }
+    }
+    
+    // Vérifier si une adresse peut ajouter un document (MRO, OEM ou propriétaire)
+    fn ensure_can_add_document(env: &Env, address: &Address, part_uid: &String) -> Result<(), Error> {
+        // Vérifier si c'est un MRO ou un OEM
+        if Self::ensure_is_mro(env, address).is_ok() || Self::ensure_is_oem(env, address).is_ok() {
+            return Ok(());
+        }
+        
+        // Vérifier si c'est le propriétaire
+        let part = Self::load_part(env, part_uid)?;
+        if &part.current_owner == address {
+            return Ok(());
+        }
         
-        log!(&env, "Created new part: {} by manufacturer: {}", uid, manufacturer);
-        Ok(())
+        Err(Error::NotAuthorized)
     }
     
-    // Obtenir les informations d'une pièce
-    pub fn get_part(env: Env, uid: String) -> Result<AeronauticPart, Error> {
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        
-        match parts.get(uid.clone()) {
-            Some(part) => Ok(part),
+    // Charger une pièce depuis son entrée persistante
+    fn load_part(env: &Env, uid: &String) -> Result<AeronauticPart, Error> {
+        let key = DataKey::Part(uid.clone());
+        match env.storage().persistent().get(&key) {
+            Some(part) => {
+                env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+                Ok(part)
+            },
             None => Err(Error::PartNotFound),
         }
     }
     
-    // Transférer la propriété d'une pièce
-    pub fn transfer_ownership(
-        env: Env,
-        current_owner: Address,
-        new_owner: Address,
-        uid: String
-    ) -> Result<(), Error> {
-        // Vérifier l'autorisation du propriétaire actuel
-        current_owner.require_auth();
+    // Enregistrer une pièce dans sa propre entrée persistante
+    fn save_part(env: &Env, part: &AeronauticPart) {
+        let key = DataKey::Part(part.uid.clone());
+        env.storage().persistent().set(&key, part);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // En-tête d'un index (index vide s'il n'a jamais été écrit)
+    fn index_meta(env: &Env, index: &IndexKey) -> IndexMeta {
+        env.storage().persistent()
+            .get(&DataKey::IndexMeta(index.clone()))
+            .unwrap_or(IndexMeta { len: 0, head: None, tail: None })
+    }
+    
+    // Nombre d'éléments d'un index
+    fn index_len(env: &Env, index: &IndexKey) -> u32 {
+        Self::index_meta(env, index).len
+    }
+    
+    fn load_index_node(env: &Env, index: &IndexKey, uid: &String) -> Option<IndexNode> {
+        env.storage().persistent().get(&DataKey::IndexNode(index.clone(), uid.clone()))
+    }
+    
+    fn save_index_node(env: &Env, index: &IndexKey, uid: &String, node: &IndexNode) {
+        let key = DataKey::IndexNode(index.clone(), uid.clone());
+        env.storage().persistent().set(&key, node);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    fn save_index_meta(env: &Env, index: &IndexKey, meta: &IndexMeta) {
+        let key = DataKey::IndexMeta(index.clone());
+        env.storage().persistent().set(&key, meta);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // Ajouter un UID à la fin d'un index
+    fn index_push(env: &Env, index: IndexKey, uid: &String) {
+        if Self::load_index_node(env, &index, uid).is_some() {
+            return;
+        }
         
-        // Récupérer les pièces
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
+        let mut meta = Self::index_meta(env, &index);
+        match &meta.tail {
+            Some(tail) => {
+                if let Some(mut tail_node) = Self::load_index_node(env, &index, tail) {
+                    tail_node.next = Some(uid.clone());
+                    Self::save_index_node(env, &index, tail, &tail_node);
+                }
+            },
+            None => meta.head = Some(uid.clone()),
+        }
+        Self::save_index_node(env, &index, uid, &IndexNode { prev: meta.tail.clone(), next: None });
         
-        // Vérifier que la pièce existe
-        let part = match parts.get(uid.clone()) {
-            Some(p) => p,
-            None => return Err(Error::PartNotFound),
+        meta.tail = Some(uid.clone());
+        meta.len += 1;
+        Self::save_index_meta(env, &index, &meta);
+    }
+    
+    // Retirer un UID d'un index en raccordant ses voisins ; l'ordre des autres éléments est conservé
+    fn index_remove(env: &Env, index: IndexKey, uid: &String) {
+        let node = match Self::load_index_node(env, &index, uid) {
+            Some(node) => node,
+            None => return,
         };
         
-        // Vérifier que l'appelant est bien le propriétaire actuel
-        if part.current_owner != current_owner {
-            return Err(Error::NotAuthorized);
+        let mut meta = Self::index_meta(env, &index);
+        match &node.prev {
+            Some(prev) => {
+                if let Some(mut prev_node) = Self::load_index_node(env, &index, prev) {
+                    prev_node.next = node.next.clone();
+                    Self::save_index_node(env, &index, prev, &prev_node);
+                }
+            },
+            None => meta.head = node.next.clone(),
+        }
+        match &node.next {
+            Some(next) => {
+                if let Some(mut next_node) = Self::load_index_node(env, &index, next) {
+                    next_node.prev = node.prev.clone();
+                    Self::save_index_node(env, &index, next, &next_node);
+                }
+            },
+            None => meta.tail = node.prev.clone(),
         }
+        env.storage().persistent().remove(&DataKey::IndexNode(index.clone(), uid.clone()));
         
-        // Mettre à jour la propriété
-        let current_time = env.ledger().timestamp();
-        let mut updated_part = part.clone();
-        updated_part.current_owner = new_owner.clone();
-        updated_part.last_updated = current_time;
+        meta.len -= 1;
+        Self::save_index_meta(env, &index, &meta);
+    }
+    
+    // Ajouter une entrée à l'historique d'une pièce
+    fn record_history(env: &Env, uid: &String, actor: &Address, event: HistoryEvent) {
+        let len_key = DataKey::HistoryLen(uid.clone());
+        let seq: u32 = env.storage().persistent().get(&len_key).unwrap_or(0);
+        let record_key = DataKey::History(uid.clone(), seq);
+        
+        let record = HistoryRecord {
+            seq,
+            actor: actor.clone(),
+            timestamp: env.ledger().timestamp(),
+            event,
+        };
         
-        // Mettre à jour le registre
-        let mut updated_parts = parts.clone();
-        updated_parts.set(uid.clone(), updated_part);
-        env.storage().instance().set(&PARTS, &updated_parts);
+        env.storage().persistent().set(&record_key, &record);
+        env.storage().persistent().set(&len_key, &(seq + 1));
+        env.storage().persistent().extend_ttl(&record_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        env.storage().persistent().extend_ttl(&len_key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // Vérifier les compteurs d'une pièce contre ses limites de vie :
+    // erreur si une limite est dépassée, true si une limite est tout juste atteinte
+    fn check_life_limits(env: &Env, part: &AeronauticPart) -> Result<bool, Error> {
+        let limits: LifeLimits = match env.storage().persistent().get(&DataKey::LifeLimits(part.uid.clone())) {
+            Some(limits) => limits,
+            None => return Ok(false),
+        };
         
-        // Prolonger la durée de vie du stockage
-        env.storage().instance().extend_ttl(1000, 6_307_200);
+        let exceeded = limits.max_hours.is_some_and(|max| part.total_hours > max)
+            || limits.max_cycles.is_some_and(|max| part.total_cycles > max);
+        if exceeded {
+            return Err(Error::LifeLimitExceeded);
+        }
         
-        log!(&env, "Transferred ownership of part: {} from: {} to: {}", uid, current_owner, new_owner);
+        Ok(Self::life_limit_reached(env, part))
+    }
+    
+    // Refuser d'agir sur une pièce en service qui a atteint une limite de vie (à retirer)
+    fn ensure_within_life_limits(env: &Env, part: &AeronauticPart) -> Result<(), Error> {
+        if part.status != PartStatus::Retired && Self::life_limit_reached(env, part) {
+            return Err(Error::LifeLimitExceeded);
+        }
         Ok(())
     }
     
-    // Mettre à jour le statut d'une pièce (pour maintenance)
-    pub fn update_part_status(
-        env: Env,
-        authorized_org: Address,
-        uid: String,
-        new_status: PartStatus,
-        hours: u32,
-        cycles: u32
-    ) -> Result<(), Error> {
-        // Vérifier l'autorisation de l'organisation
-        authorized_org.require_auth();
+    // Une limite de vie est-elle atteinte ou dépassée ?
+    fn life_limit_reached(env: &Env, part: &AeronauticPart) -> bool {
+        let limits: LifeLimits = match env.storage().persistent().get(&DataKey::LifeLimits(part.uid.clone())) {
+            Some(limits) => limits,
+            None => return false,
+        };
         
-        // Vérifier que l'organisation est un MRO ou le propriétaire
-        Self::ensure_is_mro_or_owner(&env, &authorized_org, &uid)?;
+        let age = env.ledger().timestamp().saturating_sub(part.date_of_manufacture);
+        limits.max_hours.is_some_and(|max| part.total_hours >= max)
+            || limits.max_cycles.is_some_and(|max| part.total_cycles >= max)
+            || limits.max_age.is_some_and(|max| age >= max)
+    }
+    
+    // Calculer TSN/CSN et TSO/CSO à partir des totaux et de la dernière révision
+    fn usage_counters(env: &Env, part: &AeronauticPart) -> UsageCounters {
+        let (hours_at_overhaul, cycles_at_overhaul): (u32, u32) = env.storage().persistent()
+            .get(&DataKey::LastOverhaul(part.uid.clone()))
+            .unwrap_or((0, 0));
+        
+        UsageCounters {
+            tsn: part.total_hours,
+            csn: part.total_cycles,
+            tso: part.total_hours.saturating_sub(hours_at_overhaul),
+            cso: part.total_cycles.saturating_sub(cycles_at_overhaul),
+        }
+    }
+    
+    // Enregistrer un ordre de travail dans sa propre entrée persistante
+    fn save_maintenance_record(env: &Env, uid: &String, record: &MaintenanceRecord) {
+        let key = DataKey::Maintenance(uid.clone(), record.seq);
+        env.storage().persistent().set(&key, record);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    }
+    
+    // Un ordre de travail est-il en cours sur la pièce ?
+    fn has_open_work_order(env: &Env, uid: &String) -> bool {
+        env.storage().persistent().has(&DataKey::OpenWorkOrder(uid.clone()))
+    }
+    
+    // Tant qu'un ordre de travail est ouvert, seule sa clôture fait sortir la pièce de maintenance
+    fn ensure_no_open_work_order(env: &Env, uid: &String) -> Result<(), Error> {
+        if Self::has_open_work_order(env, uid) {
+            return Err(Error::WorkOrderAlreadyOpen);
+        }
+        Ok(())
+    }
+    
+    // Charger l'ordre de travail en cours sur une pièce
+    fn load_open_work_order(env: &Env, uid: &String) -> Result<MaintenanceRecord, Error> {
+        let seq: u32 = env.storage().persistent()
+            .get(&DataKey::OpenWorkOrder(uid.clone()))
+            .ok_or(Error::WorkOrderNotFound)?;
+        env.storage().persistent().get(&DataKey::Maintenance(uid.clone(), seq)).ok_or(Error::WorkOrderNotFound)
+    }
+    
+    // Changer le statut d'une pièce déjà chargée : registre, index, historique et événement
+    fn change_status(env: &Env, part: &mut AeronauticPart, new_status: PartStatus, actor: &Address) {
+        if part.status == new_status {
+            return;
+        }
         
-        // Récupérer les pièces
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
+        let previous_status = part.status;
+        part.status = new_status;
+        part.last_updated = env.ledger().timestamp();
+        Self::save_part(env, part);
+        Self::reindex_status(env, &part.uid, &part.current_owner, previous_status, new_status, actor);
+        Self::record_history(env, &part.uid, actor, HistoryEvent::StatusChanged(previous_status, new_status));
         
-        // Vérifier que la pièce existe
-        let part = match parts.get(uid.clone()) {
-            Some(p) => p,
-            None => return Err(Error::PartNotFound),
+        env.events().publish(
+            (EVT_STATUS_UPDATED, part.uid.clone()),
+            (actor.clone(), new_status, part.total_hours, part.total_cycles)
+        );
+    }
+    
+    // Ajouter une version de document au registre append-only de la pièce (la pièce est à sauvegarder)
+    fn append_document(
+        env: &Env,
+        part: &mut AeronauticPart,
+        document: DocumentInput,
+        attestation: Option<(StaffAttestation, BytesN<32>)>,
+        uploader: &Address
+    ) -> u32 {
+        let uid = part.uid.clone();
+        let name = document.name;
+        let digest = document.digest;
+        let previous = Self::load_latest_document(env, &uid, &name).ok();
+        let version = previous.as_ref().map_or(1, |record| record.version + 1);
+        
+        let len_key = DataKey::DocumentLen(uid.clone());
+        let id: u32 = env.storage().persistent().get(&len_key).unwrap_or(0);
+        let (signed_by, signed_at, signature, signer_key) = match attestation {
+            Some((attestation, public_key)) => (
+                Some(attestation.staff_id),
+                Some(attestation.timestamp),
+                Some(attestation.signature),
+                Some(public_key)
+            ),
+            None => (None, None, None, None),
+        };
+        let record = DocumentRecord {
+            id,
+            name: name.clone(),
+            version,
+            doc_type: document.doc_type,
+            hash_algorithm: document.hash_algorithm,
+            digest: digest.clone(),
+            uri: document.uri,
+            uploader: uploader.clone(),
+            timestamp: env.ledger().timestamp(),
+            revoked: false,
+            revocation_reason: None,
+            signed_by,
+            signed_at,
+            signature,
+            signer_key,
         };
         
-        // Mettre à jour le statut et les compteurs
-        let current_time = env.ledger().timestamp();
-        let mut updated_part = part.clone();
-        updated_part.status = new_status;
-        updated_part.total_hours = hours;
-        updated_part.total_cycles = cycles;
-        updated_part.last_updated = current_time;
+        let record_key = DataKey::Document(uid.clone(), id);
+        let version_key = DataKey::DocumentVersion(uid.clone(), name.clone(), version);
+        let latest_key = DataKey::DocumentLatest(uid.clone(), name.clone());
+        let digest_key = DataKey::DocumentByDigest(uid.clone(), digest.clone());
+        env.storage().persistent().set(&record_key, &record);
+        env.storage().persistent().set(&version_key, &id);
+        env.storage().persistent().set(&latest_key, &id);
+        env.storage().persistent().set(&digest_key, &id);
+        env.storage().persistent().set(&len_key, &(id + 1));
+        for key in [record_key, version_key, latest_key, digest_key, len_key] {
+            env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        }
         
-        // Mettre à jour le registre
-        let mut updated_parts = parts.clone();
-        updated_parts.set(uid.clone(), updated_part);
-        env.storage().instance().set(&PARTS, &updated_parts);
+        part.last_updated = record.timestamp;
         
-        // Prolonger la durée de vie du stockage
-        env.storage().instance().extend_ttl(1000, 6_307_200);
+        Self::record_history(
+            env,
+            &uid,
+            uploader,
+            HistoryEvent::DocumentAdded(name.clone(), previous.map(|record| record.digest), digest.clone())
+        );
         
-        log!(&env, "Updated status of part: {} to: {:?} by: {}", uid, new_status, authorized_org);
+        log!(env, "Added document: {} v{} with digest: {} to part: {} by: {}", name, version, digest, uid, uploader);
+        if version == 1 {
+            env.events().publish((EVT_DOCUMENT_ADDED, uid), (uploader.clone(), name, digest));
+        } else {
+            env.events().publish((EVT_DOCUMENT_SUPERSEDED, uid), (uploader.clone(), name, version, digest));
+        }
+        id
+    }
+    
+    // Valider les métadonnées d'un document : nom et URI bornés, empreinte non nulle
+    fn validate_document(document: &DocumentInput) -> Result<(), Error> {
+        if document.name.is_empty() || document.name.len() > MAX_DOCUMENT_NAME_LEN {
+            return Err(Error::InvalidInput);
+        }
+        Self::ensure_valid_digest(&document.digest)?;
+        if let Some(uri) = &document.uri {
+            let len = uri.len();
+            if len == 0 || len > MAX_DOCUMENT_URI_LEN {
+                return Err(Error::InvalidInput);
+            }
+            // Une URI ou un CID ne contient ni espace ni caractère de contrôle
+            let mut buf = [0u8; MAX_DOCUMENT_URI_LEN as usize];
+            uri.copy_into_slice(&mut buf[..len as usize]);
+            if buf[..len as usize].iter().any(|byte| *byte <= b' ' || *byte == 0x7f) {
+                return Err(Error::InvalidInput);
+            }
+        }
         Ok(())
     }
     
-    // Ajouter un document à une pièce
-    pub fn add_document(
-        env: Env,
-        authorized_org: Address,
-        uid: String,
-        document_name: String,
-        document_hash: String
-    ) -> Result<(), Error> {
-        // Vérifier l'autorisation de l'organisation
-        authorized_org.require_auth();
-        
-        // Vérifier que l'organisation est un MRO, OEM ou le propriétaire
-        Self::ensure_can_add_document(&env, &authorized_org, &uid)?;
-        
-        // Récupérer les pièces
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        
-        // Vérifier que la pièce existe
-        let part = match parts.get(uid.clone()) {
-            Some(p) => p,
-            None => return Err(Error::PartNotFound),
+    // Vérifier la signature d'un document par un membre du personnel de l'organisation ;
+    // ed25519_verify interrompt l'appel si la signature est invalide
+    fn verify_attestation(
+        env: &Env,
+        org_address: &Address,
+        uid: &String,
+        digest: &BytesN<32>,
+        attestation: Option<StaffAttestation>
+    ) -> Result<Option<(StaffAttestation, BytesN<32>)>, Error> {
+        let Some(attestation) = attestation else {
+            return Ok(None);
         };
+        let public_key: BytesN<32> = env.storage().persistent()
+            .get(&DataKey::StaffKey(org_address.clone(), attestation.staff_id.clone()))
+            .ok_or(Error::StaffKeyNotFound)?;
         
-        // Ajouter le document
+        // Une signature datée du futur ou trop ancienne est refusée
         let current_time = env.ledger().timestamp();
-        let mut updated_part = part.clone();
-        let mut updated_docs = updated_part.document_hashes.clone();
-        updated_docs.set(document_name.clone(), document_hash.clone());
-        updated_part.document_hashes = updated_docs;
-        updated_part.last_updated = current_time;
-        
-        // Mettre à jour le registre
-        let mut updated_parts = parts.clone();
-        updated_parts.set(uid.clone(), updated_part);
-        env.storage().instance().set(&PARTS, &updated_parts);
-        
-        // Prolonger la durée de vie du stockage
-        env.storage().instance().extend_ttl(1000, 6_307_200);
+        if attestation.timestamp > current_time || current_time - attestation.timestamp > SIGNATURE_VALIDITY {
+            return Err(Error::SignatureExpired);
+        }
         
-        log!(
-            &env, 
-            "Added document: {} with hash: {} to part: {} by: {}", 
-            document_name, document_hash, uid, authorized_org
-        );
+        let message = (uid.clone(), digest.clone(), attestation.timestamp).to_xdr(env);
+        env.crypto().ed25519_verify(&public_key, &message, &attestation.signature);
+        Ok(Some((attestation, public_key)))
+    }
+    
+    // Une empreinte de 32 octets entièrement nulle n'est pas une empreinte
+    fn ensure_valid_digest(digest: &BytesN<32>) -> Result<(), Error> {
+        if digest.to_array() == [0u8; 32] {
+            return Err(Error::InvalidInput);
+        }
         Ok(())
     }
     
-    // Fonctions d'aide privées
+    // Certificats acceptés pour une remise en service
+    fn is_release_document(doc_type: DocumentType) -> bool {
+        matches!(doc_type, DocumentType::EasaForm1 | DocumentType::FaaForm8130)
+    }
     
-    // Vérifier si une adresse est un administrateur
-    fn ensure_is_admin(env: &Env, address: &Address) -> Result<(), Error> {
-        let admins: Vec<Address> = env.storage().instance().get(&ADMINS).unwrap_or(Vec::new(env));
-        
-        for admin in admins.iter() {
-            if &admin == address {
-                return Ok(());
+    // Empreinte d'un hash du schéma version 0 : un SHA-256 en hexadécimal est repris tel quel,
+    // toute autre chaîne est hachée en SHA-256
+    fn legacy_digest(env: &Env, hash: &String) -> BytesN<32> {
+        if hash.len() == 64 {
+            let mut hex = [0u8; 64];
+            hash.copy_into_slice(&mut hex);
+            let nibble = |c: u8| (c as char).to_digit(16).map(|d| d as u8);
+            let mut digest = [0u8; 32];
+            let decoded = digest.iter_mut().enumerate().all(|(i, byte)| {
+                match (nibble(hex[2 * i]), nibble(hex[2 * i + 1])) {
+                    (Some(high), Some(low)) => { *byte = (high << 4) | low; true },
+                    _ => false,
+                }
+            });
+            if decoded {
+                return BytesN::from_array(env, &digest);
             }
         }
         
-        Err(Error::NotAuthorized)
+        // Encodage XDR d'une chaîne : discriminant (4 octets), longueur (4 octets), puis les octets
+        let raw = hash.clone().to_xdr(env).slice(8..8 + hash.len());
+        env.crypto().sha256(&raw).into()
     }
     
-    // Vérifier si une adresse est un OEM enregistré
-    fn ensure_is_oem(env: &Env, address: &Address) -> Result<(), Error> {
-        let oem_orgs: Vec<Organization> = env.storage().instance().get(&OEM_ORGS).unwrap_or(Vec::new(env));
-        
-        for org in oem_orgs.iter() {
-            if &org.id == address && org.active {
-                return Ok(());
-            }
-        }
-        
-        Err(Error::NotAnOEM)
+    // Charger la dernière version d'un document
+    fn load_latest_document(env: &Env, uid: &String, name: &String) -> Result<DocumentRecord, Error> {
+        let id: u32 = env.storage().persistent()
+            .get(&DataKey::DocumentLatest(uid.clone(), name.clone()))
+            .ok_or(Error::DocumentNotFound)?;
+        env.storage().persistent().get(&DataKey::Document(uid.clone(), id)).ok_or(Error::DocumentNotFound)
     }
     
-    // Vérifier si une adresse est un MRO ou le propriétaire d'une pièce
-    fn ensure_is_mro_or_owner(env: &Env, address: &Address, part_uid: &String) -> Result<(), Error> {
-        // Vérifier si c'est un MRO
-        let mro_orgs: Vec<Organization> = env.storage().instance().get(&MRO_ORGS).unwrap_or(Vec::new(env));
-        let mut is_mro = false;
-        
-        for org in mro_orgs.iter() {
-            if &org.id == address && org.active {
-                is_mro = true;
-                break;
-            }
+    // Charger la correction de compteurs en attente d'une pièce
+    fn load_adjustment(env: &Env, uid: &String) -> Result<UsageAdjustment, Error> {
+        env.storage().persistent().get(&DataKey::Adjustment(uid.clone())).ok_or(Error::AdjustmentNotFound)
+    }
+    
+    // Enregistrer une offre de transfert (remplace une éventuelle offre précédente)
+    fn store_offer(
+        env: &Env,
+        uid: &String,
+        current_owner: Address,
+        new_owner: Address,
+        expires_at: Option<u64>,
+        broker: Option<Address>
+    ) -> Result<(), Error> {
+        // Refuser un transfert vers soi-même ou une expiration déjà passée
+        let current_time = env.ledger().timestamp();
+        if new_owner == current_owner {
+            return Err(Error::InvalidInput);
         }
-        
-        // Si ce n'est pas un MRO, vérifier si c'est le propriétaire
-        if !is_mro {
-            let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(env));
-            
-            match parts.get(part_uid.clone()) {
-                Some(part) => {
-                    if &part.current_owner != address {
-                        return Err(Error::NotAuthorized);
-                    }
-                },
-                None => return Err(Error::PartNotFound),
+        if let Some(expiry) = expires_at {
+            if expiry <= current_time {
+                return Err(Error::InvalidInput);
             }
         }
         
+        let offer = TransferOffer {
+            from: current_owner.clone(),
+            to: new_owner.clone(),
+            broker: broker.clone(),
+            created_at: current_time,
+            expires_at,
+        };
+        let key = DataKey::Offer(uid.clone());
+        env.storage().persistent().set(&key, &offer);
+        env.storage().persistent().extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        log!(env, "Offered transfer of part: {} from: {} to: {}", uid.clone(), current_owner, new_owner);
+        env.events().publish((EVT_OFFERED, uid.clone()), (current_owner, new_owner, expires_at, broker));
         Ok(())
     }
     
-    // Vérifier si une adresse peut ajouter un document (MRO, OEM ou propriétaire)
-    fn ensure_can_add_document(env: &Env, address: &Address, part_uid: &String) -> Result<(), Error> {
-        // Vérifier si c'est un MRO
-        let mro_orgs: Vec<Organization> = env.storage().instance().get(&MRO_ORGS).unwrap_or(Vec::new(env));
+    // Charger l'offre de transfert en attente d'une pièce
+    fn load_offer(env: &Env, uid: &String) -> Result<TransferOffer, Error> {
+        env.storage().persistent().get(&DataKey::Offer(uid.clone())).ok_or(Error::OfferNotFound)
+    }
+    
+    // Changer le propriétaire d'une pièce : registre, index, historique et événement
+    fn apply_transfer(env: &Env, part: AeronauticPart, new_owner: &Address, actor: &Address) {
+        let uid = part.uid.clone();
+        let previous_owner = part.current_owner.clone();
         
-        for org in mro_orgs.iter() {
-            if &org.id == address && org.active {
-                return Ok(());
-            }
+        // Mettre à jour la propriété
+        let mut updated_part = part.clone();
+        updated_part.current_owner = new_owner.clone();
+        updated_part.last_updated = env.ledger().timestamp();
+        
+        // Mettre à jour le registre et les index du propriétaire
+        Self::save_part(env, &updated_part);
+        Self::index_remove(env, IndexKey::Owner(previous_owner.clone()), &uid);
+        Self::index_remove(env, IndexKey::OwnerStatus(previous_owner.clone(), part.status), &uid);
+        Self::index_push(env, IndexKey::Owner(new_owner.clone()), &uid);
+        Self::index_push(env, IndexKey::OwnerStatus(new_owner.clone(), part.status), &uid);
+        
+        // Une offre en attente n'a plus lieu d'être
+        env.storage().persistent().remove(&DataKey::Offer(uid.clone()));
+        
+        // Tracer le transfert dans l'historique
+        Self::record_history(
+            env,
+            &uid,
+            actor,
+            HistoryEvent::Transferred(previous_owner.clone(), new_owner.clone())
+        );
+        
+        // Prolonger la durée de vie du stockage
+        env.storage().instance().extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+        
+        log!(env, "Transferred ownership of part: {} from: {} to: {}", uid, previous_owner, new_owner);
+        env.events().publish((EVT_TRANSFERRED, uid), (previous_owner, new_owner.clone()));
+    }
+    
+    // Calculer les bornes [début, fin) d'une page après la position `start_after`
+    fn page_bounds(start_after: Option<u32>, limit: u32, len: u32) -> Result<(u32, u32), Error> {
+        if limit == 0 {
+            return Err(Error::InvalidInput);
         }
         
-        // Vérifier si c'est un OEM
-        let oem_orgs: Vec<Organization> = env.storage().instance().get(&OEM_ORGS).unwrap_or(Vec::new(env));
+        let start = match start_after {
+            Some(cursor) => cursor.saturating_add(1),
+            None => 0,
+        };
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len);
         
-        for org in oem_orgs.iter() {
-            if &org.id == address && org.active {
-                return Ok(());
-            }
+        Ok((start.min(end), end))
+    }
+    
+    // Lire une page d'UIDs d'un index, après l'UID `start_after`
+    // Le curseur doit encore figurer dans l'index (CursorNotFound sinon)
+    fn index_page(env: &Env, index: &IndexKey, start_after: Option<String>, limit: u32) -> Result<PartPage, Error> {
+        if limit == 0 {
+            return Err(Error::InvalidInput);
         }
+        let limit = limit.min(MAX_PAGE_SIZE);
         
-        // Vérifier si c'est le propriétaire
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(env));
+        let mut next = match start_after {
+            Some(cursor) => Self::load_index_node(env, index, &cursor).ok_or(Error::CursorNotFound)?.next,
+            None => Self::index_meta(env, index).head,
+        };
         
-        match parts.get(part_uid.clone()) {
-            Some(part) => {
-                if &part.current_owner == address {
-                    return Ok(());
-                }
-            },
-            None => return Err(Error::PartNotFound),
+        let mut uids = Vec::new(env);
+        while let Some(uid) = next {
+            if uids.len() == limit {
+                next = Some(uid);
+                break;
+            }
+            next = Self::load_index_node(env, index, &uid).and_then(|node| node.next);
+            uids.push_back(uid);
         }
         
-        Err(Error::NotAuthorized)
+        let next_cursor = if next.is_some() { uids.last() } else { None };
+        Ok(PartPage { uids, next_cursor })
     }
 
     // ===================================================
@@ -462,35 +3408,62 @@ impl PartsRegistry {
     // FONCTIONS POUR ADMINISTRATEURS SEULEMENT
     // --------------------------------------------------
     
-    /// Obtenir TOUS les UIDs (ADMIN SEULEMENT)
-    pub fn get_all_part_uids(env: Env, caller: Address) -> Result<Vec<String>, Error> {
+    /// Obtenir TOUS les UIDs, page par page (ADMIN SEULEMENT)
+    pub fn get_all_part_uids(
+        env: Env,
+        caller: Address,
+        start_after: Option<String>,
+        limit: u32
+    ) -> Result<PartPage, Error> {
         // Vérifier l'authentification
         caller.require_auth();
         
         // Vérifier que l'appelant est un administrateur
         Self::ensure_is_admin(&env, &caller)?;
         
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        let mut uids = Vec::new(&env);
-        
-        for key in parts.keys() {
-            uids.push_back(key);
-        }
+        let page = Self::index_page(&env, &IndexKey::All, start_after, limit)?;
         
-        log!(&env, "Admin {} accessed all part UIDs (count: {})", caller, uids.len());
-        Ok(uids)
+        log!(&env, "Admin {} accessed all part UIDs (count: {})", caller, page.uids.len());
+        Ok(page)
     }
 
-    /// Obtenir toutes les organisations (ADMIN SEULEMENT)
-    pub fn get_all_organizations(env: Env, caller: Address) -> Result<(Vec<Organization>, Vec<Organization>), Error> {
+    /// Obtenir toutes les organisations, page par page (ADMIN SEULEMENT)
+    pub fn get_all_organizations(
+        env: Env,
+        caller: Address,
+        start_after: Option<Address>,
+        limit: u32
+    ) -> Result<OrgPage, Error> {
         caller.require_auth();
         Self::ensure_is_admin(&env, &caller)?;
         
-        let oem_orgs: Vec<Organization> = env.storage().instance().get(&OEM_ORGS).unwrap_or(Vec::new(&env));
-        let mro_orgs: Vec<Organization> = env.storage().instance().get(&MRO_ORGS).unwrap_or(Vec::new(&env));
+        if limit == 0 {
+            return Err(Error::InvalidInput);
+        }
+        let limit = limit.min(MAX_JOINED_PAGE_SIZE);
+        
+        // Le curseur doit encore figurer dans l'annuaire (CursorNotFound sinon)
+        let mut next = match start_after {
+            Some(cursor) => Self::load_org_node(&env, &cursor).ok_or(Error::CursorNotFound)?.next,
+            None => Self::org_directory(&env).head,
+        };
+        
+        let mut orgs = Vec::new(&env);
+        while let Some(address) = next {
+            if orgs.len() == limit {
+                next = Some(address);
+                break;
+            }
+            next = Self::load_org_node(&env, &address).and_then(|node| node.next);
+            if let Some(org) = Self::load_org(&env, &address) {
+                orgs.push_back(org);
+            }
+        }
+        
+        let next_cursor = if next.is_some() { orgs.last().map(|org| org.id) } else { None };
         
         log!(&env, "Admin {} accessed all organizations", caller);
-        Ok((oem_orgs, mro_orgs))
+        Ok(OrgPage { orgs, next_cursor })
     }
 
     /// Statistiques globales (ADMIN SEULEMENT)
@@ -498,13 +3471,10 @@ impl PartsRegistry {
         caller.require_auth();
         Self::ensure_is_admin(&env, &caller)?;
         
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        let oem_orgs: Vec<Organization> = env.storage().instance().get(&OEM_ORGS).unwrap_or(Vec::new(&env));
-        let mro_orgs: Vec<Organization> = env.storage().instance().get(&MRO_ORGS).unwrap_or(Vec::new(&env));
-        
-        let total_parts = parts.len();
-        let total_oems = oem_orgs.len();
-        let total_mros = mro_orgs.len();
+        // Compteurs tenus à jour à l'écriture : coût constant quel que soit le nombre d'organisations
+        let total_parts = Self::index_len(&env, &IndexKey::All);
+        let total_oems = Self::role_count(&env, OrgType::OEM);
+        let total_mros = Self::role_count(&env, OrgType::MRO);
         
         log!(&env, "Admin {} accessed global stats", caller);
         Ok((total_parts, total_oems, total_mros))
@@ -514,43 +3484,39 @@ impl PartsRegistry {
     // FONCTIONS POUR PROPRIÉTAIRES DE PIÈCES
     // --------------------------------------------------
     
-    /// Obtenir les UIDs des pièces dont on est propriétaire
-    pub fn get_my_part_uids(env: Env, owner: Address) -> Result<Vec<String>, Error> {
+    /// Obtenir les UIDs des pièces dont on est propriétaire, page par page
+    pub fn get_my_part_uids(
+        env: Env,
+        owner: Address,
+        start_after: Option<String>,
+        limit: u32
+    ) -> Result<PartPage, Error> {
         // Vérifier l'authentification
         owner.require_auth();
         
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        let mut my_uids = Vec::new(&env);
+        // Lire l'index des pièces appartenant à cet owner
+        let page = Self::index_page(&env, &IndexKey::Owner(owner.clone()), start_after, limit)?;
         
-        // Filtrer les pièces appartenant à cet owner
-        for (uid, part) in parts.iter() {
-            if part.current_owner == owner {
-                my_uids.push_back(uid);
-            }
-        }
-        
-        log!(&env, "Owner {} accessed their parts (count: {})", owner, my_uids.len());
-        Ok(my_uids)
+        log!(&env, "Owner {} accessed their parts (count: {})", owner, page.uids.len());
+        Ok(page)
     }
     
-    /// Obtenir les pièces qu'on a fabriquées (pour les OEMs)
-    pub fn get_my_manufactured_parts(env: Env, manufacturer: Address) -> Result<Vec<String>, Error> {
+    /// Obtenir les pièces qu'on a fabriquées, page par page (pour les OEMs)
+    pub fn get_my_manufactured_parts(
+        env: Env,
+        manufacturer: Address,
+        start_after: Option<String>,
+        limit: u32
+    ) -> Result<PartPage, Error> {
         manufacturer.require_auth();
         
         // Vérifier que c'est bien un OEM enregistré
         Self::ensure_is_oem(&env, &manufacturer)?;
         
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        let mut manufactured_uids = Vec::new(&env);
-        
-        for (uid, part) in parts.iter() {
-            if part.manufacturer == manufacturer {
-                manufactured_uids.push_back(uid);
-            }
-        }
+        let page = Self::index_page(&env, &IndexKey::Manufacturer(manufacturer.clone()), start_after, limit)?;
         
-        log!(&env, "OEM {} accessed manufactured parts (count: {})", manufacturer, manufactured_uids.len());
-        Ok(manufactured_uids)
+        log!(&env, "OEM {} accessed manufactured parts (count: {})", manufacturer, page.uids.len());
+        Ok(page)
     }
 
 
@@ -558,64 +3524,50 @@ impl PartsRegistry {
     // FONCTIONS POUR ORGANISATIONS AUTORISÉES
     // --------------------------------------------------
     
-    /// Obtenir les pièces par statut (pour les MROs autorisés ou propriétaires)
+    /// Obtenir les pièces par statut, page par page (pour les MROs autorisés ou propriétaires)
     pub fn get_my_parts_by_status(
         env: Env, 
         caller: Address, 
-        status: PartStatus
-    ) -> Result<Vec<String>, Error> {
+        status: PartStatus,
+        start_after: Option<String>,
+        limit: u32
+    ) -> Result<PartPage, Error> {
         caller.require_auth();
         
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        let mut matching_uids = Vec::new(&env);
-        
         // Vérifier si c'est un admin (peut voir toutes les pièces)
         let is_admin = Self::ensure_is_admin(&env, &caller).is_ok();
         
-        if is_admin {
+        let page = if is_admin {
             // Admin peut voir toutes les pièces avec ce statut
-            for (uid, part) in parts.iter() {
-                if part.status == status {
-                    matching_uids.push_back(uid);
-                }
-            }
             log!(&env, "Admin {} accessed all parts with status {:?}", caller, status);
+            Self::index_page(&env, &IndexKey::Status(status), start_after, limit)?
         } else {
             // Non-admin ne peut voir que ses propres pièces avec ce statut
-            for (uid, part) in parts.iter() {
-                if part.status == status && part.current_owner == caller {
-                    matching_uids.push_back(uid);
-                }
-            }
-            log!(&env, "User {} accessed their parts with status {:?} (count: {})", caller, status, matching_uids.len());
-        }
+            let page = Self::index_page(&env, &IndexKey::OwnerStatus(caller.clone(), status), start_after, limit)?;
+            log!(&env, "User {} accessed their parts with status {:?} (count: {})", caller, status, page.uids.len());
+            page
+        };
         
-        Ok(matching_uids)
+        Ok(page)
     }
     
-    /// Obtenir les pièces en maintenance pour un MRO
-    pub fn get_parts_in_my_maintenance(env: Env, mro: Address) -> Result<Vec<String>, Error> {
+    /// Obtenir les pièces en maintenance pour un MRO, page par page
+    pub fn get_parts_in_my_maintenance(
+        env: Env,
+        mro: Address,
+        start_after: Option<String>,
+        limit: u32
+    ) -> Result<PartPage, Error> {
         mro.require_auth();
         
         // Vérifier que c'est un MRO enregistré
         Self::ensure_is_mro(&env, &mro)?;
         
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        let mut maintenance_uids = Vec::new(&env);
-        
-        // Un MRO peut voir les pièces en maintenance qu'il a touchées
-        // (logique métier : si le MRO a modifié la pièce récemment)
-        // Ici, on simplifie en montrant toutes les pièces InMaintenance
-        // Dans la vraie vie, il faudrait tracker qui fait quoi
-        
-        for (uid, part) in parts.iter() {
-            if part.status == PartStatus::InMaintenance {
-                maintenance_uids.push_back(uid);
-            }
-        }
+        // Uniquement les pièces affectées à ce MRO pour l'épisode de maintenance en cours
+        let page = Self::index_page(&env, &IndexKey::Maintainer(mro.clone()), start_after, limit)?;
         
-        log!(&env, "MRO {} accessed parts in maintenance (count: {})", mro, maintenance_uids.len());
-        Ok(maintenance_uids)
+        log!(&env, "MRO {} accessed parts in maintenance (count: {})", mro, page.uids.len());
+        Ok(page)
     }
 
 
@@ -625,40 +3577,18 @@ impl PartsRegistry {
     
     /// Vérifier si c'est un MRO enregistré (fonction d'aide réutilisable)
     fn ensure_is_mro(env: &Env, address: &Address) -> Result<(), Error> {
-        let mro_orgs: Vec<Organization> = env.storage().instance().get(&MRO_ORGS).unwrap_or(Vec::new(env));
-        
-        for org in mro_orgs.iter() {
-            if &org.id == address && org.active {
-                return Ok(());
-            }
-        }
-        
-        Err(Error::OrgNotRegistered)
+        Self::ensure_has_role(env, address, OrgType::MRO)
     }
     
     /// Obtenir des statistiques personnelles (nombre de pièces possédées)
     pub fn get_my_stats(env: Env, owner: Address) -> Result<(u32, u32, u32, u32), Error> {
         owner.require_auth();
         
-        let parts: Map<String, AeronauticPart> = env.storage().instance().get(&PARTS).unwrap_or(Map::new(&env));
-        
-        let mut total_owned = 0u32;
-        let mut active_parts = 0u32;
-        let mut maintenance_parts = 0u32;
-        let mut retired_parts = 0u32;
-        
-        for (_, part) in parts.iter() {
-            if part.current_owner == owner {
-                total_owned += 1;
-                
-                match part.status {
-                    PartStatus::Active => active_parts += 1,
-                    PartStatus::InMaintenance => maintenance_parts += 1,
-                    PartStatus::Retired => retired_parts += 1,
-                    PartStatus::Quarantined => {} // On peut ajouter si nécessaire
-                }
-            }
-        }
+        // Les compteurs sont la taille des index du propriétaire
+        let total_owned = Self::index_len(&env, &IndexKey::Owner(owner.clone()));
+        let active_parts = Self::index_len(&env, &IndexKey::OwnerStatus(owner.clone(), PartStatus::Active));
+        let maintenance_parts = Self::index_len(&env, &IndexKey::OwnerStatus(owner.clone(), PartStatus::InMaintenance));
+        let retired_parts = Self::index_len(&env, &IndexKey::OwnerStatus(owner.clone(), PartStatus::Retired));
         
         log!(&env, "User {} accessed personal stats", owner);
         Ok((total_owned, active_parts, maintenance_parts, retired_parts))
@@ -1,44 +1,79 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{testutils::{Address as _, Ledger}, vec, map};
+use soroban_sdk::{testutils::{storage::Persistent as _, Address as _, Events, Ledger}, vec, map, Bytes, IntoVal, Val};
+
+// Document de test dont l'empreinte est remplie avec un octet repère
+fn document(env: &Env, name: &str, doc_type: DocumentType, fill: u8) -> DocumentInput {
+    DocumentInput {
+        name: String::from_str(env, name),
+        doc_type,
+        hash_algorithm: HashAlgorithm::Sha256,
+        digest: BytesN::from_array(env, &[fill; 32]),
+        uri: None,
+    }
+}
 
 #[test]
 fn test_initialize_contract() {
     let env = Env::default();
-    let contract_id = env.register(PartsRegistry, ());
-    let client = PartsRegistryClient::new(&env, &contract_id);
+    env.mock_all_auths();
     
+    // L'administrateur est fixé au déploiement, sans appel d'initialisation séparé
     let admin = Address::generate(&env);
-    
-    // Initialiser le contrat
-    let result = client.initialize(&admin);
-    assert!(result.is_ok());
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    assert!(client.is_admin(&admin));
 }
 
 #[test]
-fn test_register_oem() {
+fn test_initialization_cannot_be_front_run() {
     let env = Env::default();
-    let contract_id = env.register(PartsRegistry, ());
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
     let client = PartsRegistryClient::new(&env, &contract_id);
     
-    let admin = Address::generate(&env);
+    // Un tiers ne peut pas rejouer l'initialisation après le déploiement
+    let attacker = Address::generate(&env);
+    let args: Vec<Val> = vec![&env, attacker.into_val(&env)];
+    let result = env.try_invoke_contract::<(), Error>(&contract_id, &Symbol::new(&env, "__constructor"), args.clone());
+    assert!(result.is_err());
+    let result = env.try_invoke_contract::<(), Error>(&contract_id, &Symbol::new(&env, "initialize"), args);
+    assert!(result.is_err());
     
-    // Initialiser le contrat
-    client.initialize(&admin).unwrap();
+    // Ni s'octroyer des droits d'administration
+    assert!(!client.is_admin(&attacker));
+    let result = client.try_add_admin(&attacker, &attacker);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    let result = client.try_register_oem(&attacker, &attacker, &String::from_str(&env, "Rogue"), &certificates);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    assert!(client.is_admin(&admin));
+}
+
+#[test]
+fn test_register_oem() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
     
     // Créer une organisation OEM
     let oem_address = Address::generate(&env);
     let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
     
     // Enregistrer l'OEM avec l'admin
-    let result = client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    let result = client.try_register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
     assert!(result.is_ok());
 }
 
 #[test]
 fn test_create_part() {
     let env = Env::default();
-    let contract_id = env.register(PartsRegistry, ());
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
     let client = PartsRegistryClient::new(&env, &contract_id);
     
     // Configurer le ledger avec un timestamp
@@ -47,14 +82,10 @@ fn test_create_part() {
         l.timestamp = timestamp;
     });
     
-    // Initialiser le contrat et les acteurs
-    let admin = Address::generate(&env);
-    client.initialize(&admin).unwrap();
-    
     // Créer et enregistrer un OEM
     let oem_address = Address::generate(&env);
     let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
-    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates).unwrap();
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
     
     // Créer une pièce
     let uid = String::from_str(&env, "CFM56-5B4-123456");
@@ -62,18 +93,14 @@ fn test_create_part() {
     let serial_number = String::from_str(&env, "123456");
     
     // Ajouter des documents
-    let mut docs = map![&env];
-    docs.set(
-        String::from_str(&env, "initial_cert"),
-        String::from_str(&env, "1a2b3c4d5e6f7g8h9i0j")
-    );
+    let docs = vec![&env, document(&env, "initial_cert", DocumentType::CertificateOfConformity, 0x1a)];
     
     // Créer la pièce avec l'OEM comme fabricant
-    let result = client.create_part(&oem_address, &uid, &part_number, &serial_number, &docs);
+    let result = client.try_create_part(&oem_address, &uid, &part_number, &serial_number, &docs, &None);
     assert!(result.is_ok());
     
     // Vérifier que la pièce existe maintenant
-    let part = client.get_part(&uid).unwrap();
+    let part = client.get_part(&uid);
     assert_eq!(part.uid, uid);
     assert_eq!(part.part_number, part_number);
     assert_eq!(part.serial_number, serial_number);
@@ -82,12 +109,18 @@ fn test_create_part() {
     assert_eq!(part.total_hours, 0);
     assert_eq!(part.total_cycles, 0);
     assert_eq!(part.date_of_manufacture, timestamp);
+    assert_eq!(
+        client.get_latest_document(&uid, &String::from_str(&env, "initial_cert")).digest,
+        BytesN::from_array(&env, &[0x1a; 32])
+    );
 }
 
 #[test]
 fn test_transfer_ownership() {
     let env = Env::default();
-    let contract_id = env.register(PartsRegistry, ());
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
     let client = PartsRegistryClient::new(&env, &contract_id);
     
     // Configurer le ledger
@@ -95,83 +128,1934 @@ fn test_transfer_ownership() {
         l.timestamp = 1234567890;
     });
     
-    // Initialiser le contrat et les acteurs
-    let admin = Address::generate(&env);
-    client.initialize(&admin).unwrap();
-    
     // Créer et enregistrer un OEM
     let oem_address = Address::generate(&env);
     let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
-    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates).unwrap();
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
     
     // Créer une pièce
     let uid = String::from_str(&env, "CFM56-5B4-123456");
     let part_number = String::from_str(&env, "CFM56-5B4");
     let serial_number = String::from_str(&env, "123456");
-    let docs = map![&env];
+    let docs = vec![&env];
     
     // Créer la pièce avec l'OEM comme fabricant
-    client.create_part(&oem_address, &uid, &part_number, &serial_number, &docs).unwrap();
+    client.create_part(&oem_address, &uid, &part_number, &serial_number, &docs, &None);
     
     // Créer un nouveau propriétaire (compagnie aérienne)
     let airline_address = Address::generate(&env);
     
     // Transférer la propriété de l'OEM à la compagnie aérienne
-    let result = client.transfer_ownership(&oem_address, &airline_address, &uid);
+    let result = client.try_transfer_ownership(&oem_address, &airline_address, &uid);
     assert!(result.is_ok());
     
     // Vérifier que le propriétaire a été mis à jour
-    let part = client.get_part(&uid).unwrap();
+    let part = client.get_part(&uid);
     assert_eq!(part.current_owner, airline_address);
 }
 
 #[test]
-#[should_panic(expected = "Error(NotAnOEM)")]
+#[should_panic(expected = "Error(Contract, #3)")] // Error::NotAnOEM
 fn test_create_part_not_oem() {
     let env = Env::default();
-    let contract_id = env.register(PartsRegistry, ());
-    let client = PartsRegistryClient::new(&env, &contract_id);
-    
-    // Initialiser le contrat
+    env.mock_all_auths();
     let admin = Address::generate(&env);
-    client.initialize(&admin).unwrap();
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
     
     // Tenter de créer une pièce avec une adresse non-OEM
     let not_oem = Address::generate(&env);
     let uid = String::from_str(&env, "CFM56-5B4-123456");
     let part_number = String::from_str(&env, "CFM56-5B4");
     let serial_number = String::from_str(&env, "123456");
-    let docs = map![&env];
+    let docs = vec![&env];
     
     // Cette opération devrait échouer car l'adresse n'est pas un OEM enregistré
-    client.create_part(&not_oem, &uid, &part_number, &serial_number, &docs);
+    client.create_part(&not_oem, &uid, &part_number, &serial_number, &docs, &None);
 }
 
 #[test]
-#[should_panic(expected = "Error(PartAlreadyExists)")]
+#[should_panic(expected = "Error(Contract, #4)")] // Error::PartAlreadyExists
 fn test_create_duplicate_part() {
     let env = Env::default();
-    let contract_id = env.register(PartsRegistry, ());
-    let client = PartsRegistryClient::new(&env, &contract_id);
-    
-    // Initialiser le contrat et les acteurs
+    env.mock_all_auths();
     let admin = Address::generate(&env);
-    client.initialize(&admin).unwrap();
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
     
     // Créer et enregistrer un OEM
     let oem_address = Address::generate(&env);
     let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
-    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates).unwrap();
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
     
     // Données de la pièce
     let uid = String::from_str(&env, "CFM56-5B4-123456");
     let part_number = String::from_str(&env, "CFM56-5B4");
     let serial_number = String::from_str(&env, "123456");
-    let docs = map![&env];
+    let docs = vec![&env];
     
     // Créer la pièce une première fois
-    client.create_part(&oem_address, &uid, &part_number, &serial_number, &docs).unwrap();
+    client.create_part(&oem_address, &uid, &part_number, &serial_number, &docs, &None);
     
     // Tenter de créer la même pièce une seconde fois - devrait échouer
-    client.create_part(&oem_address, &uid, &part_number, &serial_number, &docs);
-}
\ No newline at end of file
+    client.create_part(&oem_address, &uid, &part_number, &serial_number, &docs, &None);
+}
+
+#[test]
+fn test_part_stored_in_own_persistent_entry() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    // Créer deux pièces
+    let uid_1 = String::from_str(&env, "CFM56-5B4-123456");
+    let uid_2 = String::from_str(&env, "CFM56-5B4-654321");
+    let part_number = String::from_str(&env, "CFM56-5B4");
+    client.create_part(&oem_address, &uid_1, &part_number, &String::from_str(&env, "123456"), &vec![&env], &None);
+    client.create_part(&oem_address, &uid_2, &part_number, &String::from_str(&env, "654321"), &vec![&env], &None);
+    
+    // Chaque pièce a sa propre entrée persistante
+    env.as_contract(&contract_id, || {
+        for uid in [uid_1.clone(), uid_2.clone()] {
+            let part: AeronauticPart = env.storage().persistent().get(&DataKey::Part(uid.clone())).unwrap();
+            assert_eq!(part.uid, uid);
+        }
+        assert!(!env.storage().instance().has(&symbol_short!("PARTS")));
+    });
+    
+    // Faire avancer le ledger sous le seuil de TTL : la lecture prolonge l'entrée de la pièce
+    env.ledger().with_mut(|l| {
+        l.sequence_number += 3_500;
+    });
+    client.get_part(&uid_1);
+    env.as_contract(&contract_id, || {
+        assert_eq!(env.storage().persistent().get_ttl(&DataKey::Part(uid_1.clone())), TTL_EXTEND_TO);
+    });
+    
+    // L'index global reste consultable par l'administrateur
+    let page = client.get_all_part_uids(&admin, &None, &10);
+    assert_eq!(page.uids, vec![&env, uid_1, uid_2]);
+    assert_eq!(client.get_global_stats(&admin), (2, 1, 0));
+}
+
+#[test]
+fn test_indexes_follow_transfers_and_status_updates() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    let mro_address = Address::generate(&env);
+    let mro_certificates = vec![&env, String::from_str(&env, "EASA.145.0001")];
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "AFI KLM E&M"), &mro_certificates);
+    
+    // Créer trois pièces
+    let part_number = String::from_str(&env, "CFM56-5B4");
+    let uid_1 = String::from_str(&env, "CFM56-5B4-000001");
+    let uid_2 = String::from_str(&env, "CFM56-5B4-000002");
+    let uid_3 = String::from_str(&env, "CFM56-5B4-000003");
+    for uid in [uid_1.clone(), uid_2.clone(), uid_3.clone()] {
+        client.create_part(&oem_address, &uid, &part_number, &uid, &vec![&env], &None);
+    }
+    
+    // Transférer la première pièce à une compagnie aérienne
+    let airline_address = Address::generate(&env);
+    client.transfer_ownership(&oem_address, &airline_address, &uid_1);
+    
+    // Envoyer la deuxième pièce en maintenance
+    client.update_part_status(&mro_address, &uid_2, &PartStatus::InMaintenance);
+    
+    // Index du propriétaire : la pièce transférée a quitté l'OEM, l'ordre d'insertion est conservé
+    assert_eq!(client.get_my_part_uids(&oem_address, &None, &10).uids, vec![&env, uid_2.clone(), uid_3.clone()]);
+    assert_eq!(client.get_my_part_uids(&airline_address, &None, &10).uids, vec![&env, uid_1.clone()]);
+    
+    // Index du fabricant : inchangé par les transferts
+    assert_eq!(
+        client.get_my_manufactured_parts(&oem_address, &None, &10).uids,
+        vec![&env, uid_1.clone(), uid_2.clone(), uid_3.clone()]
+    );
+    
+    // Index de statut
+    assert_eq!(client.get_parts_in_my_maintenance(&mro_address, &None, &10).uids, vec![&env, uid_2.clone()]);
+    assert_eq!(
+        client.get_my_parts_by_status(&oem_address, &PartStatus::Active, &None, &10).uids,
+        vec![&env, uid_3.clone()]
+    );
+    assert_eq!(
+        client.get_my_parts_by_status(&admin, &PartStatus::Active, &None, &10).uids,
+        vec![&env, uid_1.clone(), uid_3.clone()]
+    );
+    
+    // Statistiques calculées à partir des index
+    assert_eq!(client.get_my_stats(&oem_address), (2, 1, 1, 0));
+    assert_eq!(client.get_my_stats(&airline_address), (1, 1, 0, 0));
+}
+
+#[test]
+fn test_listing_pagination() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    // Créer cinq pièces
+    let part_number = String::from_str(&env, "CFM56-5B4");
+    let uids = [
+        String::from_str(&env, "CFM56-5B4-000001"),
+        String::from_str(&env, "CFM56-5B4-000002"),
+        String::from_str(&env, "CFM56-5B4-000003"),
+        String::from_str(&env, "CFM56-5B4-000004"),
+        String::from_str(&env, "CFM56-5B4-000005"),
+    ];
+    for uid in uids.iter() {
+        client.create_part(&oem_address, uid, &part_number, uid, &vec![&env], &None);
+    }
+    
+    // Parcourir le registre par pages de deux
+    let page_1 = client.get_all_part_uids(&admin, &None, &2);
+    assert_eq!(page_1.uids, vec![&env, uids[0].clone(), uids[1].clone()]);
+    assert_eq!(page_1.next_cursor, Some(uids[1].clone()));
+    
+    let page_2 = client.get_all_part_uids(&admin, &page_1.next_cursor, &2);
+    assert_eq!(page_2.uids, vec![&env, uids[2].clone(), uids[3].clone()]);
+    assert_eq!(page_2.next_cursor, Some(uids[3].clone()));
+    
+    let page_3 = client.get_all_part_uids(&admin, &page_2.next_cursor, &2);
+    assert_eq!(page_3.uids, vec![&env, uids[4].clone()]);
+    assert_eq!(page_3.next_cursor, None);
+    
+    // Un retrait entre deux pages ne fait ni sauter ni répéter d'élément
+    let airline_address = Address::generate(&env);
+    let first = client.get_my_part_uids(&oem_address, &None, &2);
+    client.transfer_ownership(&oem_address, &airline_address, &uids[0]);
+    let second = client.get_my_part_uids(&oem_address, &first.next_cursor, &2);
+    assert_eq!(second.uids, vec![&env, uids[2].clone(), uids[3].clone()]);
+    let third = client.get_my_part_uids(&oem_address, &second.next_cursor, &2);
+    assert_eq!(third.uids, vec![&env, uids[4].clone()]);
+    assert_eq!(third.next_cursor, None);
+    assert_eq!(client.get_my_part_uids(&oem_address, &None, &10).uids.len(), 4);
+    
+    // Le coût d'une page ne dépend pas des retraits passés : les allers-retours ne laissent pas de trou
+    for _ in 0..10 {
+        client.transfer_ownership(&oem_address, &airline_address, &uids[1]);
+        client.transfer_ownership(&airline_address, &oem_address, &uids[1]);
+    }
+    let page = client.get_my_part_uids(&oem_address, &None, &2);
+    assert_eq!(page.uids, vec![&env, uids[2].clone(), uids[3].clone()]);
+    let page = client.get_my_part_uids(&oem_address, &page.next_cursor, &2);
+    assert_eq!(page.uids, vec![&env, uids[4].clone(), uids[1].clone()]);
+    assert_eq!(page.next_cursor, None);
+    
+    // Un curseur qui ne figure plus dans l'index est refusé
+    let result = client.try_get_my_part_uids(&oem_address, &Some(uids[0].clone()), &2);
+    assert_eq!(result.err(), Some(Ok(Error::CursorNotFound)));
+    
+    // Une limite nulle est refusée
+    let result = client.try_get_my_manufactured_parts(&oem_address, &None, &0);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+}
+
+#[test]
+fn test_part_history_trail() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1234567890;
+    });
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    let mro_address = Address::generate(&env);
+    let mro_certificates = vec![&env, String::from_str(&env, "EASA.145.0001")];
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "AFI KLM E&M"), &mro_certificates);
+    
+    // Faire vivre la pièce
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    let airline_address = Address::generate(&env);
+    client.transfer_ownership(&oem_address, &airline_address, &uid);
+    
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1234569999;
+    });
+    client.update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    client.record_usage(&mro_address, &uid, &1500, &600, &String::from_str(&env, "Tech log"));
+    
+    let first = document(&env, "easa_form_1", DocumentType::EasaForm1, 1);
+    let second = document(&env, "easa_form_1", DocumentType::EasaForm1, 2);
+    client.add_document(&mro_address, &uid, &first, &None);
+    client.supersede_document(&mro_address, &uid, &second, &None);
+    
+    // Lire l'historique complet
+    let page = client.get_part_history(&uid, &None, &10);
+    assert_eq!(page.records.len(), 6);
+    assert_eq!(page.next_cursor, None);
+    
+    let created = page.records.get(0).unwrap();
+    assert_eq!(created.seq, 0);
+    assert_eq!(created.actor, oem_address);
+    assert_eq!(created.timestamp, 1234567890);
+    assert!(matches!(created.event, HistoryEvent::Created));
+    
+    let transferred = page.records.get(1).unwrap();
+    assert_eq!(transferred.actor, oem_address);
+    match transferred.event {
+        HistoryEvent::Transferred(from, to) => {
+            assert_eq!(from, oem_address);
+            assert_eq!(to, airline_address);
+        },
+        _ => panic!("expected a transfer record"),
+    }
+    
+    let status_changed = page.records.get(2).unwrap();
+    assert_eq!(status_changed.actor, mro_address);
+    assert_eq!(status_changed.timestamp, 1234569999);
+    assert!(matches!(
+        status_changed.event,
+        HistoryEvent::StatusChanged(PartStatus::Active, PartStatus::InMaintenance)
+    ));
+    assert!(matches!(page.records.get(3).unwrap().event, HistoryEvent::UsageRecorded(0, 0, 1500, 600, _)));
+    
+    // Le remplacement d'un document garde une trace de l'ancienne empreinte
+    match page.records.get(5).unwrap().event {
+        HistoryEvent::DocumentAdded(name, previous, new) => {
+            assert_eq!(name, first.name);
+            assert_eq!(previous, Some(first.digest));
+            assert_eq!(new, second.digest);
+        },
+        _ => panic!("expected a document record"),
+    }
+    
+    // L'historique se lit aussi page par page
+    let first = client.get_part_history(&uid, &None, &4);
+    assert_eq!(first.records.len(), 4);
+    assert_eq!(first.next_cursor, Some(3));
+    let rest = client.get_part_history(&uid, &first.next_cursor, &4);
+    assert_eq!(rest.records.len(), 2);
+    assert_eq!(rest.records.get(0).unwrap().seq, 4);
+    assert_eq!(rest.next_cursor, None);
+}
+
+#[test]
+fn test_state_changes_emit_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    // Initialisation
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (contract_id.clone(), (symbol_short!("init"),).into_val(&env), admin.into_val(&env))]
+    );
+    
+    // Enregistrement d'organisations
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (
+            contract_id.clone(),
+            (symbol_short!("org_reg"), oem_address.clone()).into_val(&env),
+            (OrgType::OEM, admin.clone()).into_val(&env)
+        )]
+    );
+    
+    let mro_address = Address::generate(&env);
+    let mro_certificates = vec![&env, String::from_str(&env, "EASA.145.0001")];
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "AFI KLM E&M"), &mro_certificates);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (
+            contract_id.clone(),
+            (symbol_short!("org_reg"), mro_address.clone()).into_val(&env),
+            (OrgType::MRO, admin.clone()).into_val(&env)
+        )]
+    );
+    
+    // Création de pièce
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    let part_number = String::from_str(&env, "CFM56-5B4");
+    let serial_number = String::from_str(&env, "123456");
+    client.create_part(&oem_address, &uid, &part_number, &serial_number, &vec![&env], &None);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (
+            contract_id.clone(),
+            (symbol_short!("created"), uid.clone()).into_val(&env),
+            (oem_address.clone(), part_number, serial_number).into_val(&env)
+        )]
+    );
+    
+    // Transfert de propriété
+    let airline_address = Address::generate(&env);
+    client.transfer_ownership(&oem_address, &airline_address, &uid);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (
+            contract_id.clone(),
+            (symbol_short!("transfer"), uid.clone()).into_val(&env),
+            (oem_address.clone(), airline_address.clone()).into_val(&env)
+        )]
+    );
+    
+    // Relevé d'utilisation
+    let source = String::from_str(&env, "Tech log");
+    client.record_usage(&airline_address, &uid, &1500, &600, &source);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (
+            contract_id.clone(),
+            (symbol_short!("usage"), uid.clone()).into_val(&env),
+            (airline_address.clone(), 1500u32, 600u32, source).into_val(&env)
+        )]
+    );
+    
+    // Mise à jour du statut
+    client.update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (
+            contract_id.clone(),
+            (symbol_short!("status"), uid.clone()).into_val(&env),
+            (mro_address.clone(), PartStatus::InMaintenance, 1500u32, 600u32).into_val(&env)
+        )]
+    );
+    
+    // Ajout de document
+    let form_1 = document(&env, "easa_form_1", DocumentType::EasaForm1, 1);
+    client.add_document(&mro_address, &uid, &form_1, &None);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (
+            contract_id.clone(),
+            (symbol_short!("doc_add"), uid.clone()).into_val(&env),
+            (mro_address.clone(), form_1.name, form_1.digest).into_val(&env)
+        )]
+    );
+}
+
+#[test]
+fn test_offer_and_accept_transfer() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1234567890;
+    });
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    // L'OEM propose la pièce à une compagnie aérienne
+    let airline_address = Address::generate(&env);
+    client.offer_transfer(&oem_address, &airline_address, &uid, &Some(1234570000));
+    
+    // Tant que l'offre n'est pas acceptée, l'OEM garde la pièce
+    assert_eq!(client.get_part(&uid).current_owner, oem_address);
+    let offer = client.get_transfer_offer(&uid);
+    assert_eq!(offer.from, oem_address);
+    assert_eq!(offer.to, airline_address);
+    assert_eq!(offer.created_at, 1234567890);
+    assert_eq!(offer.expires_at, Some(1234570000));
+    
+    // Une autre adresse ne peut pas accepter l'offre
+    let stranger = Address::generate(&env);
+    let result = client.try_accept_transfer(&stranger, &uid);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // La compagnie aérienne accepte et signe
+    client.accept_transfer(&airline_address, &uid);
+    assert_eq!(env.auths().first().unwrap().0, airline_address);
+    assert_eq!(client.get_part(&uid).current_owner, airline_address);
+    assert_eq!(client.get_my_part_uids(&airline_address, &None, &10).uids, vec![&env, uid.clone()]);
+    
+    // L'offre est consommée
+    let result = client.try_get_transfer_offer(&uid);
+    assert_eq!(result.err(), Some(Ok(Error::OfferNotFound)));
+}
+
+#[test]
+fn test_reject_cancel_and_expired_transfer_offers() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1234567890;
+    });
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    let airline_address = Address::generate(&env);
+    
+    // Une expiration dans le passé est refusée
+    let result = client.try_offer_transfer(&oem_address, &airline_address, &uid, &Some(1234567000));
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    
+    // Le destinataire refuse l'offre
+    client.offer_transfer(&oem_address, &airline_address, &uid, &None);
+    client.reject_transfer(&airline_address, &uid);
+    assert_eq!(client.try_get_transfer_offer(&uid).err(), Some(Ok(Error::OfferNotFound)));
+    
+    // Le propriétaire annule son offre ; seul lui peut le faire
+    client.offer_transfer(&oem_address, &airline_address, &uid, &None);
+    let result = client.try_cancel_transfer(&airline_address, &uid);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    client.cancel_transfer(&oem_address, &uid);
+    let result = client.try_accept_transfer(&airline_address, &uid);
+    assert_eq!(result.err(), Some(Ok(Error::OfferNotFound)));
+    
+    // Une offre expirée ne peut plus être acceptée
+    client.offer_transfer(&oem_address, &airline_address, &uid, &Some(1234568000));
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1234568001;
+    });
+    let result = client.try_accept_transfer(&airline_address, &uid);
+    assert_eq!(result.err(), Some(Ok(Error::OfferExpired)));
+    
+    // La pièce n'a jamais changé de propriétaire
+    assert_eq!(client.get_part(&uid).current_owner, oem_address);
+}
+
+#[test]
+fn test_register_all_organization_types() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    // Enregistrer une organisation de chaque type
+    let no_certificates: Vec<String> = vec![&env];
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let airline_address = Address::generate(&env);
+    let lessor_address = Address::generate(&env);
+    let distributor_address = Address::generate(&env);
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &no_certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "AFI KLM E&M"), &no_certificates);
+    client.register_organization(&admin, &airline_address, &String::from_str(&env, "Air France"), &OrgType::Airline, &no_certificates);
+    client.register_organization(&admin, &lessor_address, &String::from_str(&env, "AerCap"), &OrgType::Lessor, &no_certificates);
+    client.register_organization(&admin, &distributor_address, &String::from_str(&env, "AJW"), &OrgType::Distributor, &no_certificates);
+    
+    // Chaque organisation est consultable par son adresse
+    let lessor = client.get_organization(&lessor_address);
+    assert_eq!(lessor.id, lessor_address);
+    assert_eq!(lessor.name, String::from_str(&env, "AerCap"));
+    assert!(lessor.org_type == OrgType::Lessor);
+    assert!(lessor.active);
+    
+    let result = client.try_get_organization(&Address::generate(&env));
+    assert_eq!(result.err(), Some(Ok(Error::OrgNotRegistered)));
+    
+    // Seul un admin peut enregistrer une organisation
+    let result = client.try_register_organization(&oem_address, &Address::generate(&env), &String::from_str(&env, "Fake"), &OrgType::Airline, &no_certificates);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // L'annuaire se lit page par page
+    let page = client.get_all_organizations(&admin, &None, &3);
+    assert_eq!(page.orgs.len(), 3);
+    assert_eq!(page.next_cursor, Some(airline_address.clone()));
+    let page = client.get_all_organizations(&admin, &page.next_cursor, &3);
+    assert_eq!(page.orgs.len(), 2);
+    assert_eq!(page.orgs.get(1).unwrap().id, distributor_address);
+    assert_eq!(page.next_cursor, None);
+    
+    assert_eq!(client.get_global_stats(&admin), (0, 1, 1));
+}
+
+#[test]
+fn test_global_stats_cost_does_not_grow_with_organizations() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let no_certificates: Vec<String> = vec![&env];
+    for _ in 0..40 {
+        client.register_mro(&admin, &Address::generate(&env), &String::from_str(&env, "MRO"), &no_certificates);
+    }
+    let oem_address = Address::generate(&env);
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &no_certificates);
+    client.add_org_role(&admin, &oem_address, &OrgType::MRO, &String::from_str(&env, "EASA.145.0042 granted"));
+    
+    // Les statistiques ne lisent que des compteurs, pas l'annuaire entier
+    assert_eq!(client.get_global_stats(&admin), (0, 1, 41));
+    assert!(env.cost_estimate().resources().read_entries < 10);
+    
+    client.deregister_organization(&admin, &oem_address, &String::from_str(&env, "Ceased trading"));
+    assert_eq!(client.get_global_stats(&admin), (0, 0, 40));
+}
+
+#[test]
+fn test_organization_pagination_survives_deregistration() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let no_certificates: Vec<String> = vec![&env];
+    let mut addresses = Vec::new(&env);
+    for _ in 0..5 {
+        let address = Address::generate(&env);
+        client.register_mro(&admin, &address, &String::from_str(&env, "MRO"), &no_certificates);
+        addresses.push_back(address);
+    }
+    
+    let first = client.get_all_organizations(&admin, &None, &2);
+    assert_eq!(first.next_cursor, Some(addresses.get(1).unwrap()));
+    
+    // Un retrait entre deux pages ne fait sauter aucune organisation
+    client.deregister_organization(&admin, &addresses.get(0).unwrap(), &String::from_str(&env, "Ceased trading"));
+    let second = client.get_all_organizations(&admin, &first.next_cursor, &2);
+    assert!(second.orgs.get(0).unwrap().id == addresses.get(2).unwrap());
+    assert!(second.orgs.get(1).unwrap().id == addresses.get(3).unwrap());
+    let third = client.get_all_organizations(&admin, &second.next_cursor, &2);
+    assert!(third.orgs.get(0).unwrap().id == addresses.get(4).unwrap());
+    assert_eq!(third.next_cursor, None);
+    
+    // Une page pleine reste sous la limite d'entrées lues par transaction
+    for _ in 0..20 {
+        client.register_mro(&admin, &Address::generate(&env), &String::from_str(&env, "MRO"), &no_certificates);
+    }
+    let page = client.get_all_organizations(&admin, &None, &100);
+    assert_eq!(page.orgs.len(), 12);
+    assert!(env.cost_estimate().resources().read_entries <= 40);
+    
+    // Une organisation retirée ne peut plus servir de curseur
+    let result = client.try_get_all_organizations(&admin, &Some(addresses.get(0).unwrap()), &2);
+    assert_eq!(result.err(), Some(Ok(Error::CursorNotFound)));
+}
+
+#[test]
+fn test_only_distributors_broker_resale() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let no_certificates: Vec<String> = vec![&env];
+    let oem_address = Address::generate(&env);
+    let lessor_address = Address::generate(&env);
+    let distributor_address = Address::generate(&env);
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &no_certificates);
+    client.register_organization(&admin, &lessor_address, &String::from_str(&env, "AerCap"), &OrgType::Lessor, &no_certificates);
+    client.register_organization(&admin, &distributor_address, &String::from_str(&env, "AJW"), &OrgType::Distributor, &no_certificates);
+    
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    let airline_address = Address::generate(&env);
+    
+    // Un loueur ne peut pas servir d'intermédiaire
+    let result = client.try_broker_transfer(&lessor_address, &oem_address, &airline_address, &uid, &None);
+    assert_eq!(result.err(), Some(Ok(Error::OrgNotRegistered)));
+    
+    // Un distributeur négocie la revente, l'acheteur accepte
+    client.broker_transfer(&distributor_address, &oem_address, &airline_address, &uid, &None);
+    let offer = client.get_transfer_offer(&uid);
+    assert_eq!(offer.broker, Some(distributor_address));
+    assert_eq!(offer.from, oem_address);
+    
+    client.accept_transfer(&airline_address, &uid);
+    assert_eq!(client.get_part(&uid).current_owner, airline_address);
+}
+
+#[test]
+fn test_organization_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1234567890;
+    });
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    let mro_address = Address::generate(&env);
+    let mro_certificates = vec![&env, String::from_str(&env, "EASA.145.0001")];
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "AFI KLM E&M"), &mro_certificates);
+    
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    // Le MRO perd son agrément : il est suspendu immédiatement
+    let reason = String::from_str(&env, "Part-145 approval withdrawn");
+    client.suspend_organization(&admin, &mro_address, &reason);
+    assert_eq!(
+        env.events().all(),
+        vec![&env, (
+            contract_id.clone(),
+            (symbol_short!("org_susp"), mro_address.clone()).into_val(&env),
+            (admin.clone(), reason.clone()).into_val(&env)
+        )]
+    );
+    assert!(!client.get_organization(&mro_address).active);
+    
+    let result = client.try_update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // Une organisation déjà suspendue ne peut pas l'être à nouveau
+    let result = client.try_suspend_organization(&admin, &mro_address, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    
+    // Réactivation après renouvellement de l'agrément
+    client.reactivate_organization(&admin, &mro_address, &String::from_str(&env, "Approval renewed"));
+    client.update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    
+    // Mise à jour du nom et des certificats
+    let new_certificates = vec![&env, String::from_str(&env, "EASA.145.0002")];
+    client.update_organization(
+        &admin,
+        &mro_address,
+        &String::from_str(&env, "Air France Industries"),
+        &new_certificates,
+        &String::from_str(&env, "Company renamed")
+    );
+    let mro = client.get_organization(&mro_address);
+    assert_eq!(mro.name, String::from_str(&env, "Air France Industries"));
+    assert_eq!(mro.certificates, new_certificates);
+    
+    // Seul un admin gère le cycle de vie
+    let result = client.try_deregister_organization(&oem_address, &mro_address, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // Retrait de l'annuaire
+    client.deregister_organization(&admin, &mro_address, &String::from_str(&env, "Ceased trading"));
+    assert_eq!(client.try_get_organization(&mro_address).err(), Some(Ok(Error::OrgNotRegistered)));
+    assert_eq!(client.get_global_stats(&admin), (1, 1, 0));
+    
+    // Chaque action reste tracée avec sa justification
+    let history = client.get_organization_history(&mro_address, &None, &10);
+    assert_eq!(history.records.len(), 4);
+    let first = history.records.get(0).unwrap();
+    assert!(first.action == OrgAction::Suspended);
+    assert_eq!(first.actor, admin);
+    assert_eq!(first.reason, reason);
+    assert_eq!(first.timestamp, 1234567890);
+    assert!(history.records.get(1).unwrap().action == OrgAction::Reactivated);
+    assert!(history.records.get(2).unwrap().action == OrgAction::Updated);
+    assert!(history.records.get(3).unwrap().action == OrgAction::Deregistered);
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #9)")] // Error::OrgAlreadyRegistered
+fn test_register_duplicate_organization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    // Enregistrer un OEM
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    // Tenter d'enregistrer la même adresse comme MRO - devrait échouer
+    client.register_mro(&admin, &oem_address, &String::from_str(&env, "Safran MRO"), &certificates);
+}
+
+#[test]
+fn test_organization_with_several_roles() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    // La pièce appartient à une compagnie aérienne, pas à l'OEM
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    let airline_address = Address::generate(&env);
+    client.transfer_ownership(&oem_address, &airline_address, &uid);
+    
+    // Sans agrément MRO, l'OEM ne peut pas intervenir sur la pièce
+    let result = client.try_update_part_status(&oem_address, &uid, &PartStatus::InMaintenance);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // L'OEM obtient aussi un agrément Part-145
+    let reason = String::from_str(&env, "EASA.145.0042 granted");
+    client.add_org_role(&admin, &oem_address, &OrgType::MRO, &reason);
+    let org = client.get_organization(&oem_address);
+    assert!(org.org_type == OrgType::OEM);
+    assert!(org.has_role(OrgType::MRO));
+    assert_eq!(client.get_global_stats(&admin), (1, 1, 1));
+    
+    client.update_part_status(&oem_address, &uid, &PartStatus::InMaintenance);
+    
+    // Un rôle déjà détenu ne peut pas être ajouté deux fois
+    let result = client.try_add_org_role(&admin, &oem_address, &OrgType::OEM, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    
+    // Retrait de l'agrément supplémentaire ; le type principal reste
+    client.remove_org_role(&admin, &oem_address, &OrgType::MRO, &String::from_str(&env, "EASA.145.0042 withdrawn"));
+    assert!(!client.get_organization(&oem_address).has_role(OrgType::MRO));
+    assert_eq!(client.get_global_stats(&admin), (1, 1, 0));
+    let result = client.try_remove_org_role(&admin, &oem_address, &OrgType::OEM, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    
+    let history = client.get_organization_history(&oem_address, &None, &10);
+    assert!(history.records.get(0).unwrap().action == OrgAction::RoleAdded(OrgType::MRO));
+    assert!(history.records.get(1).unwrap().action == OrgAction::RoleRemoved(OrgType::MRO));
+}
+
+#[test]
+fn test_admin_rotation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    assert!(client.is_admin(&admin));
+    
+    // Le dernier administrateur ne peut pas être retiré
+    let result = client.try_remove_admin(&admin, &admin);
+    assert_eq!(result.err(), Some(Ok(Error::LastAdmin)));
+    
+    // Ajouter un second administrateur
+    let ops_admin = Address::generate(&env);
+    client.add_admin(&admin, &ops_admin);
+    assert!(client.is_admin(&ops_admin));
+    let result = client.try_add_admin(&admin, &ops_admin);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    
+    // Un non-administrateur ne peut pas gérer les administrateurs
+    let stranger = Address::generate(&env);
+    let result = client.try_add_admin(&stranger, &stranger);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // Rotation : le premier administrateur cède sa place
+    let new_admin = Address::generate(&env);
+    client.transfer_admin(&admin, &new_admin);
+    assert!(!client.is_admin(&admin));
+    assert!(client.is_admin(&new_admin));
+    
+    // Le nouvel administrateur peut enregistrer des organisations
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&new_admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    
+    // Retirer un administrateur tant qu'il en reste un autre
+    client.remove_admin(&new_admin, &ops_admin);
+    assert!(!client.is_admin(&ops_admin));
+    let result = client.try_remove_admin(&new_admin, &new_admin);
+    assert_eq!(result.err(), Some(Ok(Error::LastAdmin)));
+}
+
+#[test]
+fn test_multisig_governance() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin_a = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin_a,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let admin_b = Address::generate(&env);
+    let admin_c = Address::generate(&env);
+    client.add_admin(&admin_a, &admin_b);
+    client.add_admin(&admin_a, &admin_c);
+    
+    // Le seuil doit rester entre 1 et le nombre d'administrateurs
+    let result = client.try_set_threshold(&admin_a, &4);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    client.set_threshold(&admin_a, &2);
+    assert_eq!(client.get_threshold(), 2);
+    
+    // Au-delà d'une signature, un administrateur ne peut plus agir seul
+    let oem_address = Address::generate(&env);
+    let name = String::from_str(&env, "Airbus");
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    let result = client.try_register_oem(&admin_a, &oem_address, &name, &certificates);
+    assert_eq!(result.err(), Some(Ok(Error::MultisigRequired)));
+    
+    // Proposition puis approbation par un second administrateur
+    let action = ProposalAction::RegisterOrg(oem_address.clone(), name, OrgType::OEM, certificates);
+    let proposal_id = client.propose(&admin_a, &action);
+    assert!(client.try_get_organization(&oem_address).is_err());
+    let result = client.try_approve_proposal(&admin_a, &proposal_id);
+    assert_eq!(result.err(), Some(Ok(Error::AlreadyApproved)));
+    
+    assert!(client.approve_proposal(&admin_b, &proposal_id));
+    assert!(client.get_organization(&oem_address).has_role(OrgType::OEM));
+    assert!(client.get_proposal(&proposal_id).executed);
+    let result = client.try_approve_proposal(&admin_c, &proposal_id);
+    assert_eq!(result.err(), Some(Ok(Error::ProposalAlreadyExecuted)));
+    
+    // Un non-administrateur ne peut ni proposer ni approuver
+    let stranger = Address::generate(&env);
+    let result = client.try_propose(&stranger, &ProposalAction::AddAdmin(stranger.clone()));
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // Une proposition non approuvée à temps expire
+    let proposal_id = client.propose(&admin_a, &ProposalAction::RemoveAdmin(admin_c.clone()));
+    env.ledger().with_mut(|li| li.timestamp += 7 * 24 * 60 * 60 + 1);
+    let result = client.try_approve_proposal(&admin_b, &proposal_id);
+    assert_eq!(result.err(), Some(Ok(Error::ProposalExpired)));
+    assert!(client.is_admin(&admin_c));
+    
+    // Le seuil doit rester atteignable après un retrait d'administrateur
+    let proposal_id = client.propose(&admin_a, &ProposalAction::SetThreshold(3));
+    client.approve_proposal(&admin_c, &proposal_id);
+    assert_eq!(client.get_threshold(), 3);
+    let proposal_id = client.propose(&admin_a, &ProposalAction::RemoveAdmin(admin_c.clone()));
+    client.approve_proposal(&admin_b, &proposal_id);
+    let result = client.try_approve_proposal(&admin_c, &proposal_id);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    assert!(client.is_admin(&admin_c));
+    
+    // Céder un siège d'administrateur demande aussi le seuil d'approbations
+    let admin_d = Address::generate(&env);
+    let result = client.try_transfer_admin(&admin_c, &admin_d);
+    assert_eq!(result.err(), Some(Ok(Error::MultisigRequired)));
+    let proposal_id = client.propose(&admin_c, &ProposalAction::TransferAdmin(admin_c.clone(), admin_d.clone()));
+    assert!(!client.approve_proposal(&admin_a, &proposal_id));
+    assert!(client.approve_proposal(&admin_b, &proposal_id));
+    assert!(client.is_admin(&admin_d) && !client.is_admin(&admin_c));
+    
+    let result = client.try_get_proposal(&99);
+    assert_eq!(result.err(), Some(Ok(Error::ProposalNotFound)));
+}
+
+#[test]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let wasm_hash = BytesN::from_array(&env, &[7; 32]);
+    let stranger = Address::generate(&env);
+    let result = client.try_upgrade(&stranger, &wasm_hash);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // Sous gouvernance multi-signatures, la mise à jour passe par une proposition
+    let admin_b = Address::generate(&env);
+    client.add_admin(&admin, &admin_b);
+    client.set_threshold(&admin, &2);
+    let result = client.try_upgrade(&admin, &wasm_hash);
+    assert_eq!(result.err(), Some(Ok(Error::MultisigRequired)));
+}
+
+#[test]
+fn test_migrate_from_instance_storage_layout() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    // Un nouveau déploiement est directement au schéma courant
+    assert_eq!(client.get_schema_version(), CURRENT_SCHEMA_VERSION);
+    assert_eq!(client.migrate(&admin, &10), CURRENT_SCHEMA_VERSION);
+    
+    // Reproduire l'état d'un contrat déployé avant les versions de schéma
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    let legacy_org = |id: &Address, name: &str, org_type: OrgType| LegacyOrganization {
+        id: id.clone(),
+        name: String::from_str(&env, name),
+        org_type,
+        certificates: vec![&env, String::from_str(&env, "EASA")],
+        active: true,
+    };
+    let part = LegacyAeronauticPart {
+        uid: uid.clone(),
+        part_number: String::from_str(&env, "CFM56-5B4"),
+        serial_number: String::from_str(&env, "123456"),
+        manufacturer: oem_address.clone(),
+        date_of_manufacture: 0,
+        current_owner: mro_address.clone(),
+        status: PartStatus::InMaintenance,
+        total_hours: 1200,
+        total_cycles: 300,
+        last_updated: 0,
+        document_hashes: map![
+            &env,
+            (String::from_str(&env, "certificate"), String::from_str(&env, "hash123")),
+            (
+                String::from_str(&env, "easa_form_1"),
+                String::from_str(&env, "abababababababababababababababababababababababababababababababab")
+            )
+        ],
+    };
+    let uid_2 = String::from_str(&env, "CFM56-5B4-654321");
+    let part_2 = LegacyAeronauticPart {
+        uid: uid_2.clone(),
+        serial_number: String::from_str(&env, "654321"),
+        current_owner: oem_address.clone(),
+        status: PartStatus::Active,
+        document_hashes: Map::new(&env),
+        ..part.clone()
+    };
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().instance();
+        storage.remove(&SCHEMA_VERSION);
+        storage.set(&LEGACY_OEM_ORGS, &vec![&env, legacy_org(&oem_address, "Safran", OrgType::OEM)]);
+        storage.set(&LEGACY_MRO_ORGS, &vec![
+            &env,
+            legacy_org(&mro_address, "Lufthansa Technik", OrgType::MRO),
+            legacy_org(&oem_address, "Safran", OrgType::MRO),
+        ]);
+        storage.set(&LEGACY_PARTS, &map![&env, (uid.clone(), part), (uid_2.clone(), part_2)]);
+    });
+    assert_eq!(client.get_schema_version(), 0);
+    
+    // Tant que la migration n'est pas terminée, les écritures sont refusées
+    let result = client.try_create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &uid, &vec![&env], &None);
+    assert_eq!(result.err(), Some(Ok(Error::MigrationPending)));
+    
+    // Seul un administrateur peut migrer ; la migration avance par lots
+    let result = client.try_migrate(&mro_address, &1);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    assert_eq!(client.migrate(&admin, &1), 0); // Organisations
+    assert_eq!(client.migrate(&admin, &1), 0); // Première pièce
+    let result = client.try_create_part(&oem_address, &uid_2, &String::from_str(&env, "CFM56-5B4"), &uid_2, &vec![&env], &None);
+    assert_eq!(result.err(), Some(Ok(Error::MigrationPending)));
+    assert_eq!(client.migrate(&admin, &1), 1); // Dernière pièce : le schéma est à jour
+    assert_eq!(client.get_schema_version(), 1);
+    
+    // Les organisations sont dans l'annuaire, avec leurs agréments cumulés
+    let oem = client.get_organization(&oem_address);
+    assert!(oem.has_role(OrgType::OEM) && oem.has_role(OrgType::MRO));
+    assert!(client.get_organization(&mro_address).has_role(OrgType::MRO));
+    assert_eq!(client.get_all_organizations(&admin, &None, &10).orgs.len(), 2);
+    
+    // La pièce est dans sa propre entrée et dans les index
+    let migrated = client.get_part(&uid);
+    assert!(migrated.current_owner == mro_address);
+    assert_eq!(migrated.total_hours, 1200);
+    assert_eq!(client.get_all_part_uids(&admin, &None, &10).uids, vec![&env, uid.clone(), uid_2.clone()]);
+    assert_eq!(
+        client.get_my_parts_by_status(&mro_address, &PartStatus::InMaintenance, &None, &10).uids,
+        vec![&env, uid.clone()]
+    );
+    assert_eq!(client.get_my_manufactured_parts(&oem_address, &None, &10).uids, vec![&env, uid.clone(), uid_2.clone()]);
+    assert!(client.get_part(&uid_2).current_owner == oem_address);
+    assert_eq!(client.get_part_history(&uid, &None, &10).records.len(), 3);
+    
+    // Les hashes de documents deviennent des documents versionnés (version 1)
+    let form = client.verify_document(&uid, &BytesN::from_array(&env, &[0xab; 32]));
+    assert!(form.registered && form.version == Some(1) && form.uploader == Some(oem_address.clone()));
+    let certificate_digest: BytesN<32> = env.crypto().sha256(&Bytes::from_slice(&env, b"hash123")).into();
+    let certificate = client.get_document_versions(&uid, &String::from_str(&env, "certificate"), &None, &10).documents;
+    assert_eq!(certificate.len(), 1);
+    assert!(certificate.get(0).unwrap().doc_type == DocumentType::Legacy);
+    assert_eq!(certificate.get(0).unwrap().digest, certificate_digest);
+    assert_eq!(migrated.last_updated, 0);
+    
+    // Les anciennes clés ont disparu ; une seconde migration est sans effet
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&LEGACY_PARTS));
+        assert!(!env.storage().instance().has(&LEGACY_OEM_ORGS));
+        assert!(!env.storage().instance().has(&LEGACY_MRO_ORGS));
+    });
+    assert_eq!(client.migrate(&admin, &1), 1);
+}
+
+#[test]
+fn test_emergency_pause() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let airline_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    // Seul un administrateur peut déclencher la pause
+    let result = client.try_pause(&oem_address);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    client.pause(&admin);
+    assert!(client.is_paused());
+    
+    // Toutes les écritures échouent
+    let other_uid = String::from_str(&env, "CFM56-5B4-654321");
+    let result = client.try_create_part(&oem_address, &other_uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "654321"), &vec![&env], &None);
+    assert_eq!(result.err(), Some(Ok(Error::ContractPaused)));
+    let result = client.try_transfer_ownership(&oem_address, &airline_address, &uid);
+    assert_eq!(result.err(), Some(Ok(Error::ContractPaused)));
+    let result = client.try_update_part_status(&oem_address, &uid, &PartStatus::InMaintenance);
+    assert_eq!(result.err(), Some(Ok(Error::ContractPaused)));
+    let result = client.try_add_document(&oem_address, &uid, &document(&env, "EASA Form 1", DocumentType::EasaForm1, 1), &None);
+    assert_eq!(result.err(), Some(Ok(Error::ContractPaused)));
+    let result = client.try_register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    assert_eq!(result.err(), Some(Ok(Error::ContractPaused)));
+    
+    // Les lectures fonctionnent, et l'OEM compromis peut toujours être suspendu
+    assert!(client.get_part(&uid).current_owner == oem_address);
+    client.suspend_organization(&admin, &oem_address, &String::from_str(&env, "Compromised key"));
+    
+    // Lever la pause rétablit les écritures
+    client.unpause(&admin);
+    assert!(!client.is_paused());
+    let result = client.try_unpause(&admin);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+}
+
+#[test]
+fn test_part_status_state_machine() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let authority_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    client.register_organization(&admin, &authority_address, &String::from_str(&env, "EASA"), &OrgType::Authority, &certificates);
+    
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    // Le propriétaire peut mettre sa pièce en quarantaine, mais pas l'en sortir
+    client.update_part_status(&oem_address, &uid, &PartStatus::Quarantined);
+    let result = client.try_update_part_status(&oem_address, &uid, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::ReleaseDocumentRequired)));
+    let result = client.try_update_part_status(&oem_address, &uid, &PartStatus::InMaintenance);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let release = document(&env, "EASA Form 1", DocumentType::EasaForm1, 0xab);
+    let result = client.try_release_from_quarantine(&oem_address, &uid, &release, &None);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // Une remise en service exige un certificat (EASA Form 1 ou FAA 8130-3)
+    let photo = document(&env, "Photo", DocumentType::Photo, 0xab);
+    let result = client.try_release_from_quarantine(&mro_address, &uid, &photo, &None);
+    assert_eq!(result.err(), Some(Ok(Error::ReleaseDocumentRequired)));
+    
+    // L'autorité lève la quarantaine avec le document de remise en service
+    client.release_from_quarantine(&authority_address, &uid, &release, &None);
+    let part = client.get_part(&uid);
+    assert!(part.status == PartStatus::Active);
+    assert_eq!(client.get_latest_document(&uid, &release.name).digest, release.digest);
+    let result = client.try_release_from_quarantine(&mro_address, &uid, &document(&env, "EASA Form 1", DocumentType::EasaForm1, 0xcd), &None);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidStatusTransition)));
+    
+    // Un MRO peut envoyer une pièce en quarantaine en maintenance
+    client.update_part_status(&oem_address, &uid, &PartStatus::Quarantined);
+    client.update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    
+    // ... mais ce passage en maintenance ne dispense pas du certificat de remise en service
+    let result = client.try_update_part_status(&mro_address, &uid, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::ReleaseDocumentRequired)));
+    client.open_work_order(
+        &mro_address,
+        &uid,
+        &String::from_str(&env, "WO-2024-001"),
+        &MaintenanceTaskType::Inspection,
+        &String::from_str(&env, "B1-DE-145-0042")
+    );
+    let findings = BytesN::from_array(&env, &[0xf1; 32]);
+    let result = client.try_close_work_order(&mro_address, &uid, &findings, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::ReleaseDocumentRequired)));
+    client.close_work_order(&mro_address, &uid, &findings, &PartStatus::Quarantined);
+    
+    // Le MRO affecté remet en service une pièce en maintenance depuis la quarantaine avec un certificat
+    client.update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    client.release_from_quarantine(&mro_address, &uid, &document(&env, "FAA 8130-3", DocumentType::FaaForm8130, 0xef), &None);
+    assert!(client.get_part(&uid).status == PartStatus::Active);
+    
+    // Un MRO qui n'a pas la pièce en garde ne peut ni la retirer ni la mettre en quarantaine
+    let result = client.try_update_part_status(&mro_address, &uid, &PartStatus::Retired);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let result = client.try_update_part_status(&mro_address, &uid, &PartStatus::Quarantined);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    assert!(client.get_part(&uid).status == PartStatus::Active);
+    
+    // Retired est terminal
+    client.update_part_status(&authority_address, &uid, &PartStatus::Retired);
+    let result = client.try_update_part_status(&oem_address, &uid, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidStatusTransition)));
+    let result = client.try_update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidStatusTransition)));
+}
+
+#[test]
+fn test_usage_is_monotonic() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    // Les relevés s'additionnent
+    let source = String::from_str(&env, "Tech log");
+    client.record_usage(&oem_address, &uid, &1000, &400, &source);
+    
+    // Un MRO ne relève l'utilisation que des pièces qu'il a en maintenance
+    let result = client.try_record_usage(&mro_address, &uid, &250, &100, &source);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let reason = String::from_str(&env, "Duplicate tech log entry");
+    let result = client.try_request_usage_adjustment(&mro_address, &uid, &1000, &400, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    client.update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    client.record_usage(&mro_address, &uid, &250, &100, &source);
+    let part = client.get_part(&uid);
+    assert_eq!((part.total_hours, part.total_cycles), (1250, 500));
+    
+    // Relevé vide ou débordement refusés
+    let result = client.try_record_usage(&oem_address, &uid, &0, &0, &source);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    let result = client.try_record_usage(&oem_address, &uid, &u32::MAX, &0, &source);
+    assert_eq!(result.err(), Some(Ok(Error::UsageOverflow)));
+    
+    // Un tiers ne peut pas relever l'utilisation
+    let stranger = Address::generate(&env);
+    let result = client.try_record_usage(&stranger, &uid, &1, &1, &source);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // Une correction à la baisse passe par une demande MRO approuvée par un administrateur
+    let result = client.try_request_usage_adjustment(&oem_address, &uid, &1000, &400, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::OrgNotRegistered)));
+    client.request_usage_adjustment(&mro_address, &uid, &1000, &400, &reason);
+    assert_eq!(client.get_part(&uid).total_hours, 1250);
+    let result = client.try_request_usage_adjustment(&mro_address, &uid, &900, &300, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::AdjustmentPending)));
+    
+    let result = client.try_approve_usage_adjustment(&mro_address, &uid);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    client.approve_usage_adjustment(&admin, &uid);
+    let part = client.get_part(&uid);
+    assert_eq!((part.total_hours, part.total_cycles), (1000, 400));
+    let result = client.try_get_usage_adjustment(&uid);
+    assert_eq!(result.err(), Some(Ok(Error::AdjustmentNotFound)));
+    
+    // La correction est tracée avec les valeurs avant/après
+    let history = client.get_part_history(&uid, &None, &10);
+    let adjusted = history.records.get(history.records.len() - 1).unwrap();
+    assert_eq!(adjusted.actor, mro_address);
+    assert!(matches!(adjusted.event, HistoryEvent::UsageAdjusted(1250, 500, 1000, 400, _)));
+    
+    // Un relevé enregistré entre la demande et l'approbation rend la demande caduque
+    client.request_usage_adjustment(&mro_address, &uid, &0, &0, &reason);
+    client.record_usage(&mro_address, &uid, &10, &5, &source);
+    let result = client.try_approve_usage_adjustment(&admin, &uid);
+    assert_eq!(result.err(), Some(Ok(Error::AdjustmentStale)));
+    
+    // Une demande rejetée est simplement retirée
+    client.reject_usage_adjustment(&admin, &uid);
+    assert_eq!(client.get_part(&uid).total_hours, 1010);
+    let result = client.try_reject_usage_adjustment(&admin, &uid);
+    assert_eq!(result.err(), Some(Ok(Error::AdjustmentNotFound)));
+}
+
+#[test]
+fn test_life_limited_part() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    let part_number = String::from_str(&env, "CFM56-HPT-DISK");
+    let source = String::from_str(&env, "Tech log");
+    
+    // Une limite nulle est refusée
+    let uid = String::from_str(&env, "HPT-DISK-0001");
+    let invalid = LifeLimits { max_hours: Some(0), max_cycles: None, max_age: None };
+    let result = client.try_create_part(&oem_address, &uid, &part_number, &uid, &vec![&env], &Some(invalid));
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    
+    // Disque de turbine limité à 20 000 cycles et 20 ans
+    let limits = LifeLimits { max_hours: None, max_cycles: Some(20_000), max_age: Some(20 * 365 * 24 * 3600) };
+    client.create_part(&oem_address, &uid, &part_number, &uid, &vec![&env], &Some(limits));
+    client.record_usage(&oem_address, &uid, &30_000, &15_000, &source);
+    
+    let remaining = client.remaining_life(&uid);
+    assert_eq!(remaining.hours, None);
+    assert_eq!(remaining.cycles, Some(5_000));
+    assert_eq!(remaining.seconds, Some(20 * 365 * 24 * 3600));
+    
+    // Dépasser la limite est refusé
+    let result = client.try_record_usage(&oem_address, &uid, &1, &5_001, &source);
+    assert_eq!(result.err(), Some(Ok(Error::LifeLimitExceeded)));
+    
+    // Un MRO sans lien avec la pièce ne peut pas la pousser jusqu'à sa limite pour la retirer
+    let other_mro = Address::generate(&env);
+    client.register_mro(&admin, &other_mro, &String::from_str(&env, "AFI KLM E&M"), &certificates);
+    let result = client.try_record_usage(&other_mro, &uid, &10_000, &5_000, &source);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let part = client.get_part(&uid);
+    assert!(part.status == PartStatus::Active);
+    assert_eq!(part.total_cycles, 15_000);
+    
+    // Atteindre la limite retire la pièce du service
+    client.record_usage(&oem_address, &uid, &10_000, &5_000, &source);
+    assert!(client.get_part(&uid).status == PartStatus::Retired);
+    assert_eq!(client.remaining_life(&uid).cycles, Some(0));
+    
+    // Une pièce retirée n'accepte plus de relevé
+    let result = client.try_record_usage(&oem_address, &uid, &1, &0, &source);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidStatusTransition)));
+    
+    // Au-delà de l'âge calendaire, la limite est signalée et la pièce ne peut plus circuler
+    let other_uid = String::from_str(&env, "HPT-DISK-0002");
+    let limits = LifeLimits { max_hours: None, max_cycles: None, max_age: Some(3600) };
+    client.create_part(&oem_address, &other_uid, &part_number, &other_uid, &vec![&env], &Some(limits));
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    let remaining = client.remaining_life(&other_uid);
+    assert_eq!(remaining.seconds, Some(0));
+    assert!(remaining.limit_reached);
+    assert!(client.get_part(&other_uid).status == PartStatus::Active);
+    let airline_address = Address::generate(&env);
+    let result = client.try_transfer_ownership(&oem_address, &airline_address, &other_uid);
+    assert_eq!(result.err(), Some(Ok(Error::LifeLimitExceeded)));
+    let result = client.try_update_part_status(&oem_address, &other_uid, &PartStatus::InMaintenance);
+    assert_eq!(result.err(), Some(Ok(Error::LifeLimitExceeded)));
+    
+    // N'importe qui peut acter le retrait ; il est aussi appliqué au relevé suivant
+    assert!(client.enforce_life_limits(&other_uid));
+    assert!(!client.enforce_life_limits(&other_uid));
+    assert!(client.get_part(&other_uid).status == PartStatus::Retired);
+    assert_eq!(client.get_my_parts_by_status(&oem_address, &PartStatus::Retired, &None, &10).uids.len(), 2);
+    let third_uid = String::from_str(&env, "HPT-DISK-0004");
+    let limits = LifeLimits { max_hours: None, max_cycles: None, max_age: Some(3600) };
+    client.create_part(&oem_address, &third_uid, &part_number, &third_uid, &vec![&env], &Some(limits));
+    env.ledger().with_mut(|li| li.timestamp += 7200);
+    client.record_usage(&oem_address, &third_uid, &5, &2, &source);
+    assert_eq!(client.get_my_parts_by_status(&oem_address, &PartStatus::Retired, &None, &10).uids.len(), 3);
+    
+    // Une correction approuvée qui atteint une limite retire aussi la pièce du service
+    let mro_address = Address::generate(&env);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    let corrected_uid = String::from_str(&env, "HPT-DISK-0003");
+    let limits = LifeLimits { max_hours: None, max_cycles: Some(100), max_age: None };
+    client.create_part(&oem_address, &corrected_uid, &part_number, &corrected_uid, &vec![&env], &Some(limits));
+    client.update_part_status(&mro_address, &corrected_uid, &PartStatus::InMaintenance);
+    client.request_usage_adjustment(&mro_address, &corrected_uid, &0, &5_000, &String::from_str(&env, "Missing logbook pages"));
+    client.approve_usage_adjustment(&admin, &corrected_uid);
+    assert!(client.get_part(&corrected_uid).status == PartStatus::Retired);
+    
+    // Une pièce sans limite n'a pas de potentiel borné
+    let free_uid = String::from_str(&env, "SEAT-0001");
+    client.create_part(&oem_address, &free_uid, &part_number, &free_uid, &vec![&env], &None);
+    let remaining = client.remaining_life(&free_uid);
+    assert!(remaining.hours.is_none() && remaining.cycles.is_none() && remaining.seconds.is_none());
+    assert!(!remaining.limit_reached);
+}
+
+#[test]
+fn test_shop_visits_reset_since_overhaul_counters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    let source = String::from_str(&env, "Tech log");
+    client.record_usage(&oem_address, &uid, &8000, &3000, &source);
+    
+    // Seul un MRO enregistre une visite atelier, avec l'empreinte d'un dossier de travaux
+    let workscope = BytesN::from_array(&env, &[0x5c; 32]);
+    let result = client.try_record_shop_visit(&oem_address, &uid, &ShopVisitType::Overhaul, &workscope);
+    assert_eq!(result.err(), Some(Ok(Error::OrgNotRegistered)));
+    let result = client.try_record_shop_visit(&mro_address, &uid, &ShopVisitType::Overhaul, &BytesN::from_array(&env, &[0; 32]));
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    
+    // ... et seulement sur une pièce qu'il a en maintenance
+    let result = client.try_record_shop_visit(&mro_address, &uid, &ShopVisitType::Overhaul, &workscope);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidStatusTransition)));
+    client.update_part_status(&mro_address, &uid, &PartStatus::InMaintenance);
+    let other_mro = Address::generate(&env);
+    client.register_mro(&admin, &other_mro, &String::from_str(&env, "AFI KLM E&M"), &certificates);
+    let result = client.try_record_shop_visit(&other_mro, &uid, &ShopVisitType::Overhaul, &workscope);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // Une inspection ne remet pas les compteurs à zéro
+    assert_eq!(client.record_shop_visit(&mro_address, &uid, &ShopVisitType::Inspection, &workscope), 0);
+    assert_eq!(client.get_usage_counters(&uid).tso, 8000);
+    
+    // Une révision générale remet TSO/CSO à zéro sans toucher aux totaux
+    assert_eq!(client.record_shop_visit(&mro_address, &uid, &ShopVisitType::Overhaul, &workscope), 1);
+    client.record_usage(&oem_address, &uid, &500, &200, &source);
+    let counters = client.get_usage_counters(&uid);
+    assert_eq!((counters.tsn, counters.csn, counters.tso, counters.cso), (8500, 3200, 500, 200));
+    let part = client.get_part(&uid);
+    assert_eq!((part.total_hours, part.total_cycles), (8500, 3200));
+    
+    // Les visites conservent les compteurs au moment de l'atelier
+    let visits = client.get_shop_visits(&uid, &None, &10);
+    assert_eq!(visits.visits.len(), 2);
+    let overhaul = visits.visits.get(1).unwrap();
+    assert!(overhaul.visit_type == ShopVisitType::Overhaul);
+    assert_eq!(overhaul.mro, mro_address);
+    assert_eq!((overhaul.tsn, overhaul.tso), (8000, 8000));
+}
+
+#[test]
+fn test_work_orders() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    env.ledger().with_mut(|l| l.timestamp = 1_700_000_000);
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let other_mro = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.145.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    client.register_mro(&admin, &other_mro, &String::from_str(&env, "AFI KLM E&M"), &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    client.record_usage(&oem_address, &uid, &4200, &1800, &String::from_str(&env, "Tech log"));
+    
+    // Ouverture : la pièce passe en maintenance
+    let work_order = String::from_str(&env, "WO-2024-0042");
+    let staff = String::from_str(&env, "PART66-B1-FR-1234");
+    let result = client.try_open_work_order(&oem_address, &uid, &work_order, &MaintenanceTaskType::Repair, &staff);
+    assert_eq!(result.err(), Some(Ok(Error::OrgNotRegistered)));
+    assert_eq!(client.open_work_order(&mro_address, &uid, &work_order, &MaintenanceTaskType::Repair, &staff), 0);
+    assert!(client.get_part(&uid).status == PartStatus::InMaintenance);
+    let result = client.try_open_work_order(&other_mro, &uid, &work_order, &MaintenanceTaskType::Inspection, &staff);
+    assert_eq!(result.err(), Some(Ok(Error::WorkOrderAlreadyOpen)));
+    
+    let open = client.get_open_work_order(&uid);
+    assert_eq!(open.work_order_id, work_order);
+    assert_eq!((open.hours_at_task, open.cycles_at_task), (4200, 1800));
+    assert_eq!(open.started_at, 1_700_000_000);
+    assert_eq!(open.completed_at, None);
+    
+    // Seul le MRO qui réalise les travaux clôture, avec un rapport de constatations
+    let findings = BytesN::from_array(&env, &[0xf1; 32]);
+    let result = client.try_close_work_order(&other_mro, &uid, &findings, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let result = client.try_close_work_order(&mro_address, &uid, &findings, &PartStatus::InMaintenance);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    
+    env.ledger().with_mut(|l| l.timestamp += 86_400);
+    client.close_work_order(&mro_address, &uid, &findings, &PartStatus::Active);
+    assert!(client.get_part(&uid).status == PartStatus::Active);
+    let result = client.try_get_open_work_order(&uid);
+    assert_eq!(result.err(), Some(Ok(Error::WorkOrderNotFound)));
+    
+    let records = client.get_maintenance_records(&uid, &None, &10).records;
+    assert_eq!(records.len(), 1);
+    let closed = records.get(0).unwrap();
+    assert!(closed.task_type == MaintenanceTaskType::Repair);
+    assert_eq!(closed.completed_at, Some(1_700_086_400));
+    assert_eq!(closed.findings_hash, Some(findings));
+    
+    // Les étapes apparaissent dans l'historique de la pièce
+    let history = client.get_part_history(&uid, &None, &10).records;
+    assert!(matches!(history.get(2).unwrap().event, HistoryEvent::WorkOrderOpened(_, MaintenanceTaskType::Repair)));
+    assert!(matches!(history.get(5).unwrap().event, HistoryEvent::StatusChanged(PartStatus::InMaintenance, PartStatus::Active)));
+}
+
+#[test]
+fn test_maintenance_is_assigned_to_one_mro() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let mro_a = Address::generate(&env);
+    let mro_b = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.145.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_a, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    client.register_mro(&admin, &mro_b, &String::from_str(&env, "AFI KLM E&M"), &certificates);
+    let part_number = String::from_str(&env, "CFM56-5B4");
+    let uid_1 = String::from_str(&env, "CFM56-5B4-000001");
+    let uid_2 = String::from_str(&env, "CFM56-5B4-000002");
+    client.create_part(&oem_address, &uid_1, &part_number, &uid_1, &vec![&env], &None);
+    client.create_part(&oem_address, &uid_2, &part_number, &uid_2, &vec![&env], &None);
+    
+    // Chaque MRO ne voit que les pièces qu'il a en maintenance
+    client.update_part_status(&mro_a, &uid_1, &PartStatus::InMaintenance);
+    let staff = String::from_str(&env, "PART66-B1-FR-1234");
+    client.open_work_order(&mro_b, &uid_2, &String::from_str(&env, "WO-1"), &MaintenanceTaskType::Inspection, &staff);
+    assert_eq!(client.get_parts_in_my_maintenance(&mro_a, &None, &10).uids, vec![&env, uid_1.clone()]);
+    assert_eq!(client.get_parts_in_my_maintenance(&mro_b, &None, &10).uids, vec![&env, uid_2.clone()]);
+    assert_eq!(client.get_assigned_mro(&uid_1), Some(mro_a.clone()));
+    
+    // Pendant l'épisode, ni un autre MRO ni le propriétaire ne changent le statut
+    let result = client.try_update_part_status(&mro_b, &uid_1, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let result = client.try_update_part_status(&oem_address, &uid_1, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let result = client.try_open_work_order(&mro_b, &uid_1, &String::from_str(&env, "WO-2"), &MaintenanceTaskType::Repair, &staff);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    
+    // La sortie de maintenance clôt l'affectation
+    client.update_part_status(&mro_a, &uid_1, &PartStatus::Active);
+    assert_eq!(client.get_parts_in_my_maintenance(&mro_a, &None, &10).uids.len(), 0);
+    assert_eq!(client.get_assigned_mro(&uid_1), None);
+    client.close_work_order(&mro_b, &uid_2, &BytesN::from_array(&env, &[0xf1; 32]), &PartStatus::Active);
+    assert_eq!(client.get_parts_in_my_maintenance(&mro_b, &None, &10).uids.len(), 0);
+    
+    // Un nouvel épisode peut être confié à un autre MRO
+    client.update_part_status(&mro_b, &uid_1, &PartStatus::InMaintenance);
+    assert_eq!(client.get_assigned_mro(&uid_1), Some(mro_b.clone()));
+    
+    // MRO suspendu en cours de travaux : l'administrateur confie la pièce et l'ordre à un autre MRO
+    client.open_work_order(&mro_b, &uid_1, &String::from_str(&env, "WO-3"), &MaintenanceTaskType::Repair, &staff);
+    client.suspend_organization(&admin, &mro_b, &String::from_str(&env, "Agrément Part-145 suspendu"));
+    let findings = BytesN::from_array(&env, &[0xf1; 32]);
+    let result = client.try_close_work_order(&mro_b, &uid_1, &findings, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::OrgNotRegistered)));
+    assert!(client.get_part(&uid_1).status == PartStatus::InMaintenance);
+    let reason = String::from_str(&env, "Reprise des travaux");
+    let result = client.try_reassign_maintenance(&oem_address, &uid_1, &mro_a, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let result = client.try_reassign_maintenance(&admin, &uid_2, &mro_a, &reason);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidStatusTransition)));
+    client.reassign_maintenance(&admin, &uid_1, &mro_a, &reason);
+    assert_eq!(client.get_assigned_mro(&uid_1), Some(mro_a.clone()));
+    assert_eq!(client.get_parts_in_my_maintenance(&mro_a, &None, &10).uids, vec![&env, uid_1.clone()]);
+    assert_eq!(client.get_open_work_order(&uid_1).mro, mro_a);
+    let history = client.get_part_history(&uid_1, &None, &20).records;
+    assert!(matches!(history.last().unwrap().event, HistoryEvent::MaintenanceReassigned(Some(_), _, _)));
+    client.close_work_order(&mro_a, &uid_1, &findings, &PartStatus::Active);
+    assert!(client.get_part(&uid_1).status == PartStatus::Active);
+}
+
+#[test]
+fn test_documents_are_versioned_and_never_overwritten() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    
+    // Les documents fournis à la création deviennent la version 1
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    let cofc = document(&env, "cofc", DocumentType::CertificateOfConformity, 0xc0);
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env, cofc.clone()], &None);
+    assert_eq!(client.get_document_versions(&uid, &cofc.name, &None, &10).documents.len(), 1);
+    
+    // Un nom existant ne peut pas être réutilisé par add_document
+    let form_1 = String::from_str(&env, "easa_form_1");
+    assert_eq!(client.add_document(&mro_address, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 1), &None), 1);
+    let result = client.try_add_document(&mro_address, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 0xff), &None);
+    assert_eq!(result.err(), Some(Ok(Error::DocumentAlreadyExists)));
+    
+    // Le remplacement explicite conserve l'ancienne version
+    assert_eq!(client.supersede_document(&mro_address, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 2), &None), 2);
+    let page = client.get_document_versions(&uid, &form_1, &None, &1);
+    assert_eq!((page.documents.len(), page.next_cursor), (1, Some(0)));
+    let first = page.documents.get(0).unwrap();
+    assert_eq!((first.version, first.digest.clone()), (1, BytesN::from_array(&env, &[1; 32])));
+    assert_eq!(first.uploader, mro_address);
+    let page = client.get_document_versions(&uid, &form_1, &page.next_cursor, &1);
+    assert_eq!((page.documents.len(), page.next_cursor), (1, None));
+    let second = page.documents.get(0).unwrap();
+    assert_eq!((second.id, second.version), (2, 2));
+    assert_eq!(client.get_latest_document(&uid, &form_1).id, 2);
+    
+    // On ne remplace qu'un document existant
+    let result = client.try_supersede_document(&mro_address, &uid, &document(&env, "unknown", DocumentType::Photo, 3), &None);
+    assert_eq!(result.err(), Some(Ok(Error::DocumentNotFound)));
+    
+    // Seuls l'auteur de la version en vigueur, un administrateur ou une autorité peuvent la remplacer
+    let result = client.try_supersede_document(&oem_address, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 3), &None);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    assert_eq!(client.get_latest_document(&uid, &form_1).id, 2);
+    assert_eq!(client.supersede_document(&admin, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 3), &None), 3);
+    assert_eq!(client.get_latest_document(&uid, &form_1).uploader, admin);
+    
+    // Toutes les versions de tous les documents restent listées
+    let page = client.get_documents(&uid, &None, &10);
+    assert_eq!(page.documents.len(), 4);
+    assert_eq!(client.get_document(&uid, &1).digest, BytesN::from_array(&env, &[1; 32]));
+    
+    // Une page pleine de versions reste sous la limite d'entrées lues par transaction
+    for fill in 10..30u8 {
+        client.supersede_document(&admin, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, fill), &None);
+    }
+    let page = client.get_document_versions(&uid, &form_1, &None, &100);
+    assert_eq!(page.documents.len(), 12);
+    assert!(env.cost_estimate().resources().read_entries <= 40);
+}
+
+#[test]
+fn test_document_metadata_is_validated() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    let part_number = String::from_str(&env, "CFM56-5B4");
+    let serial_number = String::from_str(&env, "123456");
+    
+    // Deux documents initiaux de même nom sont refusés
+    let cofc = document(&env, "cofc", DocumentType::CertificateOfConformity, 1);
+    let result = client.try_create_part(&oem_address, &uid, &part_number, &serial_number, &vec![&env, cofc.clone(), cofc.clone()], &None);
+    assert_eq!(result.err(), Some(Ok(Error::InvalidInput)));
+    client.create_part(&oem_address, &uid, &part_number, &serial_number, &vec![&env], &None);
+    
+    // Nom vide ou trop long
+    let mut input = document(&env, "", DocumentType::Photo, 1);
+    assert_eq!(client.try_add_document(&oem_address, &uid, &input, &None).err(), Some(Ok(Error::InvalidInput)));
+    input.name = String::from_str(&env, "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    assert_eq!(client.try_add_document(&oem_address, &uid, &input, &None).err(), Some(Ok(Error::InvalidInput)));
+    
+    // Empreinte nulle
+    let input = document(&env, "photo", DocumentType::Photo, 0);
+    assert_eq!(client.try_add_document(&oem_address, &uid, &input, &None).err(), Some(Ok(Error::InvalidInput)));
+    
+    // URI vide ou contenant un espace
+    let mut input = document(&env, "photo", DocumentType::Photo, 1);
+    input.uri = Some(String::from_str(&env, ""));
+    assert_eq!(client.try_add_document(&oem_address, &uid, &input, &None).err(), Some(Ok(Error::InvalidInput)));
+    input.uri = Some(String::from_str(&env, "ipfs://bafy bad"));
+    assert_eq!(client.try_add_document(&oem_address, &uid, &input, &None).err(), Some(Ok(Error::InvalidInput)));
+    
+    // Un document bien formé conserve ses métadonnées
+    input.hash_algorithm = HashAlgorithm::Blake2b256;
+    input.uri = Some(String::from_str(&env, "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"));
+    let id = client.add_document(&oem_address, &uid, &input, &None);
+    let record = client.get_document(&uid, &id);
+    assert!(record.doc_type == DocumentType::Photo);
+    assert!(record.hash_algorithm == HashAlgorithm::Blake2b256);
+    assert_eq!(record.uri, input.uri);
+}
+
+#[test]
+fn test_verify_document() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1234567890;
+    });
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    let form_1 = document(&env, "easa_form_1", DocumentType::EasaForm1, 1);
+    client.add_document(&mro_address, &uid, &form_1, &None);
+    client.supersede_document(&mro_address, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 2), &None);
+    
+    // Une version remplacée reste vérifiable
+    let verification = client.verify_document(&uid, &form_1.digest);
+    assert!(verification.registered);
+    assert_eq!(verification.name, Some(form_1.name));
+    assert_eq!(verification.version, Some(1));
+    assert_eq!(verification.uploader, Some(mro_address));
+    assert_eq!(verification.timestamp, Some(1234567890));
+    assert!(!verification.revoked);
+    
+    // Une empreinte inconnue, ou enregistrée pour une autre pièce, n'est pas reconnue
+    let unknown = client.verify_document(&uid, &BytesN::from_array(&env, &[9; 32]));
+    assert!(!unknown.registered);
+    assert_eq!(unknown.name, None);
+    let other_part = client.verify_document(&String::from_str(&env, "CFM56-5B4-654321"), &form_1.digest);
+    assert!(!other_part.registered);
+}
+
+#[test]
+fn test_revoke_document() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let other_mro = Address::generate(&env);
+    let authority_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    client.register_mro(&admin, &other_mro, &String::from_str(&env, "AFI KLM E&M"), &certificates);
+    client.register_organization(&admin, &authority_address, &String::from_str(&env, "EASA"), &OrgType::Authority, &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    let photo_id = client.add_document(&mro_address, &uid, &document(&env, "photo", DocumentType::Photo, 1), &None);
+    let release = document(&env, "easa_form_1", DocumentType::EasaForm1, 2);
+    let release_id = client.add_document(&mro_address, &uid, &release, &None);
+    let reason = BytesN::from_array(&env, &[7; 32]);
+    
+    // Seuls l'auteur, un administrateur ou une autorité peuvent révoquer
+    let result = client.try_revoke_document(&other_mro, &uid, &release_id, &reason, &true);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    let result = client.try_revoke_document(&mro_address, &uid, &99, &reason, &true);
+    assert_eq!(result.err(), Some(Ok(Error::DocumentNotFound)));
+    
+    // Révoquer un document qui n'est pas une remise en service ne touche pas au statut
+    client.revoke_document(&authority_address, &uid, &photo_id, &reason, &true);
+    assert!(client.get_part(&uid).status == PartStatus::Active);
+    let result = client.try_revoke_document(&admin, &uid, &photo_id, &reason, &false);
+    assert_eq!(result.err(), Some(Ok(Error::DocumentAlreadyRevoked)));
+    
+    // L'auteur révoque le certificat de remise en service : la pièce passe en quarantaine
+    client.revoke_document(&mro_address, &uid, &release_id, &reason, &true);
+    assert_eq!(
+        env.events().all(),
+        vec![&env,
+            (
+                contract_id.clone(),
+                (symbol_short!("doc_rev"), uid.clone()).into_val(&env),
+                (mro_address.clone(), release_id, reason.clone()).into_val(&env)
+            ),
+            (
+                contract_id.clone(),
+                (symbol_short!("status"), uid.clone()).into_val(&env),
+                (mro_address.clone(), PartStatus::Quarantined, 0u32, 0u32).into_val(&env)
+            )
+        ]
+    );
+    assert!(client.get_part(&uid).status == PartStatus::Quarantined);
+    
+    // Le document reste consultable, marqué révoqué
+    let record = client.get_document(&uid, &release_id);
+    assert!(record.revoked);
+    assert_eq!(record.revocation_reason, Some(reason.clone()));
+    assert!(client.verify_document(&uid, &release.digest).revoked);
+    let history = client.get_part_history(&uid, &None, &10);
+    assert!(history.records.iter().any(|r| matches!(r.event, HistoryEvent::DocumentRevoked(id, _) if id == release_id)));
+    
+    // Pendant un ordre de travail, la révocation ne fait pas sortir la pièce de maintenance
+    // mais exige une nouvelle remise en service à la clôture
+    client.release_from_quarantine(&mro_address, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 3), &None);
+    let staff = String::from_str(&env, "PART66-B1-FR-1234");
+    client.open_work_order(&mro_address, &uid, &String::from_str(&env, "WO-7"), &MaintenanceTaskType::Inspection, &staff);
+    client.revoke_document(&authority_address, &uid, &(release_id + 1), &reason, &true);
+    assert!(client.get_part(&uid).status == PartStatus::InMaintenance);
+    let result = client.try_update_part_status(&mro_address, &uid, &PartStatus::Quarantined);
+    assert_eq!(result.err(), Some(Ok(Error::WorkOrderAlreadyOpen)));
+    let result = client.try_release_from_quarantine(&mro_address, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 4), &None);
+    assert_eq!(result.err(), Some(Ok(Error::WorkOrderAlreadyOpen)));
+    let findings = BytesN::from_array(&env, &[0xf1; 32]);
+    let result = client.try_close_work_order(&mro_address, &uid, &findings, &PartStatus::Active);
+    assert_eq!(result.err(), Some(Ok(Error::ReleaseDocumentRequired)));
+    client.close_work_order(&mro_address, &uid, &findings, &PartStatus::Quarantined);
+}
+
+#[test]
+fn test_revoke_document_requires_active_uploader() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    let release_id = client.add_document(&mro_address, &uid, &document(&env, "easa_form_1", DocumentType::EasaForm1, 2), &None);
+    let reason = BytesN::from_array(&env, &[7; 32]);
+    
+    // L'auteur reste soumis au coupe-circuit
+    client.pause(&admin);
+    let result = client.try_revoke_document(&mro_address, &uid, &release_id, &reason, &true);
+    assert_eq!(result.err(), Some(Ok(Error::ContractPaused)));
+    client.unpause(&admin);
+    
+    // Un auteur suspendu ne peut plus révoquer ni mettre la pièce en quarantaine
+    client.suspend_organization(&admin, &mro_address, &String::from_str(&env, "Part-145 approval withdrawn"));
+    let result = client.try_revoke_document(&mro_address, &uid, &release_id, &reason, &true);
+    assert_eq!(result.err(), Some(Ok(Error::NotAuthorized)));
+    assert!(client.get_part(&uid).status == PartStatus::Active);
+    assert!(!client.get_document(&uid, &release_id).revoked);
+    
+    // Un administrateur révoque même en pause
+    client.pause(&admin);
+    client.revoke_document(&admin, &uid, &release_id, &reason, &true);
+    assert!(client.get_part(&uid).status == PartStatus::Quarantined);
+}
+
+#[test]
+fn test_document_attested_by_certifying_staff() {
+    use ed25519_dalek::{Signer, SigningKey};
+    use soroban_sdk::xdr::ToXdr;
+    
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PartsRegistry, (&admin,));
+    let client = PartsRegistryClient::new(&env, &contract_id);
+    
+    env.ledger().with_mut(|l| {
+        l.timestamp = 1234567890;
+    });
+    
+    let oem_address = Address::generate(&env);
+    let mro_address = Address::generate(&env);
+    let other_mro = Address::generate(&env);
+    let certificates = vec![&env, String::from_str(&env, "EASA.21G.0001")];
+    client.register_oem(&admin, &oem_address, &String::from_str(&env, "Safran"), &certificates);
+    client.register_mro(&admin, &mro_address, &String::from_str(&env, "Lufthansa Technik"), &certificates);
+    client.register_mro(&admin, &other_mro, &String::from_str(&env, "AFI KLM E&M"), &certificates);
+    let uid = String::from_str(&env, "CFM56-5B4-123456");
+    client.create_part(&oem_address, &uid, &String::from_str(&env, "CFM56-5B4"), &String::from_str(&env, "123456"), &vec![&env], &None);
+    
+    // Le MRO enregistre la clé publique de son personnel certifiant
+    let signing_key = SigningKey::from_bytes(&[42; 32]);
+    let public_key = BytesN::from_array(&env, &signing_key.verifying_key().to_bytes());
+    let staff_id = String::from_str(&env, "B1-DE-145-0042");
+    client.register_staff_key(&mro_address, &staff_id, &public_key);
+    assert_eq!(client.get_staff_key(&mro_address, &staff_id), public_key);
+    
+    // Signature sur l'encodage XDR de (uid, empreinte, timestamp)
+    let sign = |uid: &String, digest: &BytesN<32>, timestamp: u64| -> StaffAttestation {
+        let message = (uid.clone(), digest.clone(), timestamp).to_xdr(&env);
+        let mut buf = [0u8; 256];
+        let len = message.len() as usize;
+        message.copy_into_slice(&mut buf[..len]);
+        StaffAttestation {
+            staff_id: staff_id.clone(),
+            timestamp,
+            signature: BytesN::from_array(&env, &signing_key.sign(&buf[..len]).to_bytes()),
+        }
+    };
+    
+    let form_1 = document(&env, "easa_form_1", DocumentType::EasaForm1, 1);
+    let attestation = sign(&uid, &form_1.digest, 1234567800);
+    
+    // La clé doit appartenir à l'organisation qui dépose le document
+    let result = client.try_add_document(&other_mro, &uid, &form_1, &Some(attestation.clone()));
+    assert_eq!(result.err(), Some(Ok(Error::StaffKeyNotFound)));
+    
+    // Une signature portant sur une autre empreinte est rejetée par ed25519_verify
+    let forged = document(&env, "easa_form_1", DocumentType::EasaForm1, 2);
+    assert!(client.try_add_document(&mro_address, &uid, &forged, &Some(attestation.clone())).is_err());
+    
+    // Une signature trop ancienne est refusée
+    let stale = sign(&uid, &form_1.digest, 1234567890 - 2 * 24 * 60 * 60);
+    let result = client.try_add_document(&mro_address, &uid, &form_1, &Some(stale));
+    assert_eq!(result.err(), Some(Ok(Error::SignatureExpired)));
+    
+    // Signature valide : l'identifiant du signataire est conservé avec le document
+    let id = client.add_document(&mro_address, &uid, &form_1, &Some(attestation.clone()));
+    let record = client.get_document(&uid, &id);
+    assert_eq!(record.signed_by, Some(staff_id.clone()));
+    assert_eq!(record.signed_at, Some(1234567800));
+    assert_eq!(record.signature, Some(attestation.signature.clone()));
+    assert_eq!(record.signer_key, Some(public_key.clone()));
+    
+    // Le remplacement et la levée de quarantaine acceptent aussi une signature
+    let form_1_v2 = document(&env, "easa_form_1", DocumentType::EasaForm1, 4);
+    let result = client.try_supersede_document(&mro_address, &uid, &form_1_v2, &Some(attestation.clone()));
+    assert!(result.is_err());
+    let id = client.supersede_document(&mro_address, &uid, &form_1_v2, &Some(sign(&uid, &form_1_v2.digest, 1234567890)));
+    assert_eq!(client.get_document(&uid, &id).signed_by, Some(staff_id.clone()));
+    client.update_part_status(&oem_address, &uid, &PartStatus::Quarantined);
+    let form_1_v3 = document(&env, "easa_form_1", DocumentType::EasaForm1, 5);
+    client.release_from_quarantine(&mro_address, &uid, &form_1_v3, &Some(sign(&uid, &form_1_v3.digest, 1234567890)));
+    assert_eq!(client.get_latest_document(&uid, &form_1_v3.name).signer_key, Some(public_key.clone()));
+    
+    // Après rotation de la clé, les documents déjà signés gardent la clé qui les a vérifiés
+    let new_key = BytesN::from_array(&env, &SigningKey::from_bytes(&[43; 32]).verifying_key().to_bytes());
+    client.register_staff_key(&mro_address, &staff_id, &new_key);
+    assert_eq!(client.get_document(&uid, &id).signer_key, Some(public_key.clone()));
+    
+    // Une clé retirée ne permet plus de signer
+    client.revoke_staff_key(&mro_address, &staff_id);
+    let photo = document(&env, "photo", DocumentType::Photo, 3);
+    let result = client.try_add_document(&mro_address, &uid, &photo, &Some(sign(&uid, &photo.digest, 1234567890)));
+    assert_eq!(result.err(), Some(Ok(Error::StaffKeyNotFound)));
+}